@@ -0,0 +1,39 @@
+// Darkness overlay drawn over the whole view. Lights cut holes into it.
+// Only a uniform buffer is used so this also runs on WebGL2 and software
+// renderers (no storage buffers, no compute).
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+const MAX_LIGHTS: u32 = 32u;
+
+struct PointLight {
+    // xy: world position, z: radius
+    position_radius: vec4<f32>,
+    // rgb: colour, a: intensity
+    color: vec4<f32>,
+};
+
+struct Lighting {
+    // rgb: colour of the darkness, a: how dark unlit areas are
+    ambient: vec4<f32>,
+    light_count: u32,
+    lights: array<PointLight, MAX_LIGHTS>,
+};
+
+@group(2) @binding(0) var<uniform> lighting: Lighting;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    var light = vec3<f32>(0.0);
+    for (var i = 0u; i < min(lighting.light_count, MAX_LIGHTS); i++) {
+        let l = lighting.lights[i];
+        let d = distance(mesh.world_position.xy, l.position_radius.xy);
+        let falloff = clamp(1.0 - d / l.position_radius.z, 0.0, 1.0);
+        light += l.color.rgb * l.color.a * falloff * falloff;
+    }
+
+    let brightness = clamp(max(light.r, max(light.g, light.b)), 0.0, 1.0);
+    let darkness = lighting.ambient.a * (1.0 - brightness);
+    // What is left of the darkness takes the colour of the light, for a warm glow
+    let tint = mix(lighting.ambient.rgb, clamp(light, vec3<f32>(0.0), vec3<f32>(1.0)), brightness);
+    return vec4<f32>(tint, max(darkness, brightness * 0.15));
+}
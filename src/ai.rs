@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+/// Result of ticking a behaviour tree node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Success,
+    Failure,
+    Running,
+}
+
+pub type ConditionFn<B> = fn(&B) -> bool;
+pub type ActionFn<B> = fn(&mut B) -> Status;
+
+/// A behaviour tree node working on a blackboard `B`.
+/// The blackboard is a plain struct an enemy system fills from its components
+/// before ticking the tree, and reads the results back from afterwards.
+pub enum Node<B> {
+    /// Runs children in order until one doesn't succeed
+    Sequence(Vec<Node<B>>),
+    /// Runs children in order until one doesn't fail
+    Selector(Vec<Node<B>>),
+    /// Swaps success and failure of its child
+    Inverter(Box<Node<B>>),
+    /// Runs its child and always succeeds
+    Succeeder(Box<Node<B>>),
+    Condition(&'static str, ConditionFn<B>),
+    Action(&'static str, ActionFn<B>),
+}
+
+impl<B> Node<B> {
+    /// Evaluate the node, remembering the name of the last action that ran
+    pub fn tick(&self, blackboard: &mut B, active: &mut Option<&'static str>) -> Status {
+        match self {
+            Node::Sequence(children) => {
+                for child in children {
+                    match child.tick(blackboard, active) {
+                        Status::Success => continue,
+                        status => return status,
+                    }
+                }
+                Status::Success
+            }
+            Node::Selector(children) => {
+                for child in children {
+                    match child.tick(blackboard, active) {
+                        Status::Failure => continue,
+                        status => return status,
+                    }
+                }
+                Status::Failure
+            }
+            Node::Inverter(child) => match child.tick(blackboard, active) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            Node::Succeeder(child) => {
+                child.tick(blackboard, active);
+                Status::Success
+            }
+            Node::Condition(_, condition) => {
+                if condition(blackboard) {
+                    Status::Success
+                } else {
+                    Status::Failure
+                }
+            }
+            Node::Action(name, action) => {
+                *active = Some(name);
+                action(blackboard)
+            }
+        }
+    }
+}
+
+// Small helpers so trees read top to bottom
+pub fn sequence<B>(children: Vec<Node<B>>) -> Node<B> {
+    Node::Sequence(children)
+}
+
+pub fn selector<B>(children: Vec<Node<B>>) -> Node<B> {
+    Node::Selector(children)
+}
+
+pub fn inverter<B>(child: Node<B>) -> Node<B> {
+    Node::Inverter(Box::new(child))
+}
+
+pub fn succeeder<B>(child: Node<B>) -> Node<B> {
+    Node::Succeeder(Box::new(child))
+}
+
+pub fn condition<B>(name: &'static str, condition: ConditionFn<B>) -> Node<B> {
+    Node::Condition(name, condition)
+}
+
+pub fn action<B>(name: &'static str, action: ActionFn<B>) -> Node<B> {
+    Node::Action(name, action)
+}
+
+/// Behaviour tree attached to an enemy. The tree itself is shared, so
+/// cloning the component for every spawned enemy is cheap.
+#[derive(Component)]
+pub struct BehaviourTree<B: Send + Sync + 'static> {
+    root: Arc<Node<B>>,
+    /// Name of the action that ran on the last tick, handy for debugging
+    pub active_action: Option<&'static str>,
+}
+
+impl<B: Send + Sync + 'static> BehaviourTree<B> {
+    pub fn new(root: Node<B>) -> Self {
+        Self {
+            root: Arc::new(root),
+            active_action: None,
+        }
+    }
+
+    pub fn tick(&mut self, blackboard: &mut B) -> Status {
+        let mut active = None;
+        let status = self.root.tick(blackboard, &mut active);
+        self.active_action = active;
+        status
+    }
+}
+
+impl<B: Send + Sync + 'static> Clone for BehaviourTree<B> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            active_action: None,
+        }
+    }
+}
+
+/// Ordering for enemy AI: first gather what enemies know, then let them decide
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AiSet {
+    Sense,
+    Think,
+}
+
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(Update, AiSet::Sense.before(AiSet::Think));
+    }
+}
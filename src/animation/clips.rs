@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// A run of frames in a sprite sheet
+#[derive(Deserialize, Clone, Debug)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub last: usize,
+    pub fps: u8,
+    /// One-shot clips stop on their last frame and report that they finished
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+    /// Clip to continue with once a one-shot clip finished
+    #[serde(default)]
+    pub next: Option<String>,
+    /// Names sent as `AnimationEvent`s when a frame comes up, keyed by sprite index
+    #[serde(default)]
+    pub events: HashMap<usize, Vec<String>>,
+    /// Named ranges of sprite indices, e.g. the frames where an attack can hit
+    #[serde(default)]
+    pub windows: HashMap<String, (usize, usize)>,
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+/// Every clip of one sprite sheet, loaded from a `.anim.ron` file
+#[derive(Asset, TypePath, Deserialize, Debug)]
+pub struct AnimationLibrary {
+    pub clips: HashMap<String, AnimationClip>,
+    /// Gameplay state name to clip name. States without an entry play the
+    /// clip with the same name.
+    #[serde(default)]
+    pub states: HashMap<String, String>,
+}
+
+impl AnimationLibrary {
+    pub fn clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    /// Name of the clip played for a state
+    pub fn clip_for_state<'a>(&'a self, state: &'a str) -> &'a str {
+        self.states.get(state).map(String::as_str).unwrap_or(state)
+    }
+}
+
+#[derive(Debug)]
+pub enum AnimationLibraryError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for AnimationLibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimationLibraryError::Io(err) => write!(f, "could not read animations: {err}"),
+            AnimationLibraryError::Ron(err) => write!(f, "invalid animations: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for AnimationLibraryError {}
+
+impl From<std::io::Error> for AnimationLibraryError {
+    fn from(err: std::io::Error) -> Self {
+        AnimationLibraryError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for AnimationLibraryError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        AnimationLibraryError::Ron(err)
+    }
+}
+
+#[derive(Default)]
+pub struct AnimationLibraryLoader;
+
+impl AssetLoader for AnimationLibraryLoader {
+    type Asset = AnimationLibrary;
+    type Settings = ();
+    type Error = AnimationLibraryError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.ron"]
+    }
+}
@@ -0,0 +1,253 @@
+mod clips;
+
+pub use clips::{AnimationClip, AnimationLibrary, AnimationLibraryLoader};
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::fireball::DeathFade;
+
+#[derive(Component, PartialEq, Clone, Copy, Debug)]
+pub enum SpriteState {
+    Idle,
+    Running,
+}
+impl Default for SpriteState {
+    fn default() -> Self {
+        SpriteState::Idle
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct AnimationConfig {
+    pub first_sprite_index: usize,
+    pub last_sprite_index: usize,
+    pub frame_timer: Timer,
+    pub current_frame: usize,
+    pub fps: u8,
+    /// Start over after the last frame, otherwise hold it and send `AnimationFinished`
+    pub looping: bool,
+    pub finished: bool,
+    /// See `AnimationClip::events`
+    pub events: HashMap<usize, Vec<String>>,
+    /// See `AnimationClip::windows`
+    pub windows: HashMap<String, (usize, usize)>,
+    // The frame just came up and its events are not sent yet
+    frame_entered: bool,
+}
+
+impl AnimationConfig {
+    pub fn new(first: usize, last: usize, fps: u8) -> Self {
+        Self {
+            first_sprite_index: first,
+            last_sprite_index: last,
+            frame_timer: Self::timer_from_fps(fps),
+            current_frame: first,
+            fps,
+            looping: true,
+            finished: false,
+            events: HashMap::new(),
+            windows: HashMap::new(),
+            frame_entered: true,
+        }
+    }
+
+    pub fn from_clip(clip: &AnimationClip) -> Self {
+        Self {
+            looping: clip.looping,
+            events: clip.events.clone(),
+            windows: clip.windows.clone(),
+            ..Self::new(clip.first, clip.last, clip.fps)
+        }
+    }
+
+    /// Whether the frame on screen is inside the named window
+    pub fn in_window(&self, name: &str) -> bool {
+        self.windows
+            .get(name)
+            .is_some_and(|&(first, last)| (first..=last).contains(&self.current_frame))
+    }
+
+    pub fn timer_from_fps(fps: u8) -> Timer {
+        Timer::new(Duration::from_secs_f32(1.0 / (fps as f32)), TimerMode::Repeating)
+    }
+}
+
+/// Sent once when a one-shot animation played its last frame
+#[derive(Event)]
+pub struct AnimationFinished {
+    pub entity: Entity,
+}
+
+/// Sent when a frame with events comes up, once per event name
+#[derive(Event)]
+pub struct AnimationEvent {
+    pub entity: Entity,
+    pub name: String,
+}
+
+/// Picks the clip to play from a gameplay state. Gameplay code only calls
+/// `set_state`, the clips and their order live in the animation library.
+#[derive(Component)]
+pub struct AnimationStateMachine {
+    pub library: Handle<AnimationLibrary>,
+    state: String,
+    // State the current clip was picked for, None until the library loaded
+    applied_state: Option<String>,
+    clip: Option<String>,
+}
+
+impl AnimationStateMachine {
+    pub fn new(library: Handle<AnimationLibrary>, state: &str) -> Self {
+        Self {
+            library,
+            state: state.to_string(),
+            applied_state: None,
+            clip: None,
+        }
+    }
+
+    pub fn set_state(&mut self, state: &str) {
+        if self.state != state {
+            self.state = state.to_string();
+        }
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Clip currently playing
+    pub fn clip(&self) -> Option<&str> {
+        self.clip.as_deref()
+    }
+}
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<AnimationLibrary>()
+            .register_asset_loader(AnimationLibraryLoader)
+            .add_event::<AnimationFinished>()
+            .add_event::<AnimationEvent>()
+            .add_systems(Update, (
+                drive_animation_state_machines,
+                execute_animations.after(drive_animation_state_machines),
+                continue_finished_clips.after(execute_animations),
+            ));
+    }
+}
+
+// Handle animations
+pub fn execute_animations(
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut AnimationConfig, &mut Sprite), Without<DeathFade>>,
+    mut finished_events: EventWriter<AnimationFinished>,
+    mut animation_events: EventWriter<AnimationEvent>,
+) {
+    for (entity, mut config, mut sprite) in &mut query {
+        // Tick the animation timer
+        config.frame_timer.tick(time.delta());
+
+        // If it has been displayed for the correct amount of time (calculated from fps in anim. config)
+        if config.frame_timer.just_finished() {
+            if let Some(atlas) = &mut sprite.texture_atlas {
+                // If the animation needs to start again
+                if atlas.index >= config.last_sprite_index {
+                    if config.looping {
+                        atlas.index = config.first_sprite_index;
+                        config.frame_entered = true;
+                    } else if !config.finished {
+                        config.finished = true;
+                        finished_events.send(AnimationFinished { entity });
+                    }
+                } else {
+                    atlas.index += 1;
+                    config.frame_entered = true;
+                }
+
+                // Update the current frame tracker
+                config.current_frame = atlas.index;
+            }
+        }
+
+        // Includes the first frame of a clip that just started
+        if config.frame_entered {
+            config.frame_entered = false;
+            if let Some(names) = config.events.get(&config.current_frame) {
+                for name in names {
+                    animation_events.send(AnimationEvent { entity, name: name.clone() });
+                }
+            }
+        }
+    }
+}
+
+fn play_clip(
+    commands: &mut Commands,
+    entity: Entity,
+    config: Option<Mut<AnimationConfig>>,
+    sprite: &mut Sprite,
+    clip: &AnimationClip,
+) {
+    let new_config = AnimationConfig::from_clip(clip);
+    match config {
+        Some(mut config) => *config = new_config,
+        None => {
+            commands.entity(entity).insert(new_config);
+        }
+    }
+    if let Some(atlas) = &mut sprite.texture_atlas {
+        atlas.index = clip.first;
+    }
+}
+
+pub fn drive_animation_state_machines(
+    mut commands: Commands,
+    libraries: Res<Assets<AnimationLibrary>>,
+    mut query: Query<(Entity, &mut AnimationStateMachine, Option<&mut AnimationConfig>, &mut Sprite), Without<DeathFade>>,
+) {
+    for (entity, mut machine, config, mut sprite) in query.iter_mut() {
+        if machine.applied_state.as_deref() == Some(machine.state.as_str()) {
+            continue;
+        }
+        let Some(library) = libraries.get(&machine.library) else {
+            continue; // Keep whatever is playing until the clips are loaded
+        };
+
+        let clip_name = library.clip_for_state(&machine.state).to_string();
+        match library.clip(&clip_name) {
+            Some(clip) => play_clip(&mut commands, entity, config, &mut sprite, clip),
+            None => warn!("No animation clip '{}' for state '{}'", clip_name, machine.state),
+        }
+        machine.applied_state = Some(machine.state.clone());
+        machine.clip = Some(clip_name);
+    }
+}
+
+// One-shot clips can hand over to another clip without a state change
+fn continue_finished_clips(
+    mut commands: Commands,
+    mut finished_events: EventReader<AnimationFinished>,
+    libraries: Res<Assets<AnimationLibrary>>,
+    mut query: Query<(&mut AnimationStateMachine, Option<&mut AnimationConfig>, &mut Sprite)>,
+) {
+    for event in finished_events.read() {
+        let Ok((mut machine, config, mut sprite)) = query.get_mut(event.entity) else {
+            continue;
+        };
+        let Some(library) = libraries.get(&machine.library) else {
+            continue;
+        };
+        let Some(next) = machine.clip().and_then(|clip| library.clip(clip)).and_then(|clip| clip.next.clone()) else {
+            continue;
+        };
+
+        match library.clip(&next) {
+            Some(clip) => play_clip(&mut commands, event.entity, config, &mut sprite, clip),
+            None => warn!("Animation clip '{}' does not exist", next),
+        }
+        machine.clip = Some(next);
+    }
+}
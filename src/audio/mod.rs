@@ -0,0 +1,92 @@
+mod music;
+mod sfx;
+
+pub use sfx::{PlaySfx, Sfx, SfxLibrary};
+
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+
+const VOLUME_STEP: f32 = 0.1;
+
+// Separate kira channels so each kind of sound has its own volume
+#[derive(Resource)]
+pub struct MusicChannel;
+
+#[derive(Resource)]
+pub struct SfxChannel;
+
+#[derive(Resource)]
+pub struct UiChannel;
+
+/// Volume of each channel, from 0 (muted) to 1
+#[derive(Resource, Clone)]
+pub struct AudioSettings {
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            music: 1.0,
+            sfx: 0.8,
+            ui: 0.6,
+        }
+    }
+}
+
+pub struct AudioPlugin;
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(bevy_kira_audio::AudioPlugin)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<SfxChannel>()
+            .add_audio_channel::<UiChannel>()
+            .init_resource::<AudioSettings>()
+            .add_plugins((music::MusicPlugin, sfx::SfxPlugin))
+            .add_systems(Update, (adjust_volume, apply_volume).chain());
+    }
+}
+
+// F1/F2 music, F3/F4 sound effects, F5/F6 interface sounds
+fn adjust_volume(kbd: Res<ButtonInput<KeyCode>>, mut settings: ResMut<AudioSettings>) {
+    // Only flag the settings as changed when a key actually moved a volume
+    let mut changed = false;
+    let volumes = settings.bypass_change_detection();
+    let channels = [
+        (KeyCode::F1, KeyCode::F2, &mut volumes.music, "Music"),
+        (KeyCode::F3, KeyCode::F4, &mut volumes.sfx, "Sound effects"),
+        (KeyCode::F5, KeyCode::F6, &mut volumes.ui, "Interface"),
+    ];
+    for (down, up, volume, name) in channels {
+        let step = if kbd.just_pressed(down) {
+            -VOLUME_STEP
+        } else if kbd.just_pressed(up) {
+            VOLUME_STEP
+        } else {
+            continue;
+        };
+        *volume = (*volume + step).clamp(0.0, 1.0);
+        changed = true;
+        info!("{} volume: {:.0}%", name, *volume * 100.0);
+    }
+    if changed {
+        settings.set_changed();
+    }
+}
+
+fn apply_volume(
+    settings: Res<AudioSettings>,
+    music: Res<AudioChannel<MusicChannel>>,
+    sfx: Res<AudioChannel<SfxChannel>>,
+    ui: Res<AudioChannel<UiChannel>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    music.set_volume(settings.music as f64);
+    sfx.set_volume(settings.sfx as f64);
+    ui.set_volume(settings.ui as f64);
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use std::time::Duration;
+
+use crate::audio::MusicChannel;
+use crate::loading::{AppState, RequiredAssets};
+use crate::orc::boss::Boss;
+use crate::orc::{OrcEnemy, OrcPerception, OrcState};
+use crate::player_code::{Health, Player, PLAYER_MAX_HEALTH};
+
+/// Volume of every layer at full intensity
+const MUSIC_VOLUME: f64 = 0.25;
+/// Orcs hunting the player at once for the combat layer to fully take over
+const FULL_COMBAT_ORCS: f32 = 3.0;
+/// Below this share of health any fight plays at full intensity
+const LOW_HEALTH: f32 = 0.35;
+/// Seconds the combat music lingers after the last orc lost track of the player
+const COMBAT_LINGER: f32 = 4.0;
+const CROSSFADE: Duration = Duration::from_millis(1500);
+
+/// Layers all loop in sync, the director only moves their volumes
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum MusicLayer {
+    Calm,
+    Combat,
+    Boss,
+}
+
+impl MusicLayer {
+    const ALL: [MusicLayer; 3] = [MusicLayer::Calm, MusicLayer::Combat, MusicLayer::Boss];
+
+    fn path(self) -> &'static str {
+        match self {
+            MusicLayer::Calm => "audio/ARTHUROS01.ogg",
+            MusicLayer::Combat => "audio/music/combat.ogg",
+            MusicLayer::Boss => "audio/music/boss.ogg",
+        }
+    }
+}
+
+// Source of each layer, then its playing instance and target volume
+#[derive(Resource, Default)]
+struct MusicDirector {
+    sources: Vec<(MusicLayer, Handle<AudioSource>)>,
+    layers: Vec<(MusicLayer, Handle<AudioInstance>, f32)>,
+    linger: f32,
+}
+
+pub(super) struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicDirector>()
+            .add_systems(Startup, load_music)
+            .add_systems(OnExit(AppState::Loading), start_music)
+            .add_systems(Update, direct_music);
+    }
+}
+
+fn load_music(
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    mut director: ResMut<MusicDirector>,
+) {
+    director.sources = MusicLayer::ALL
+        .into_iter()
+        .map(|layer| (layer, required.optional(&asset_server, layer.path())))
+        .collect();
+}
+
+// Missing layers are left out, so their part of the mix stays silent
+fn start_music(
+    music: Res<AudioChannel<MusicChannel>>,
+    required: Res<RequiredAssets>,
+    mut director: ResMut<MusicDirector>,
+) {
+    let sources = std::mem::take(&mut director.sources);
+    for (layer, source) in sources {
+        if required.is_missing(&source) {
+            continue;
+        }
+        let volume = if layer == MusicLayer::Calm { 1.0 } else { 0.0 };
+        let instance = music.play(source)
+            // Loop the audio from 0.5 seconds skipping the intro
+            .loop_from(0.5)
+            // Fade-in with a dynamic easing over 2 seconds
+            .fade_in(AudioTween::new(Duration::from_secs(2), AudioEasing::OutPowi(2)))
+            .with_volume(volume as f64 * MUSIC_VOLUME)
+            .handle();
+        director.layers.push((layer, instance, volume));
+    }
+
+    info!("Background music started");
+}
+
+// Blend from calm to combat with the number of hunting orcs, the boss layer
+// replaces both while the boss fights
+fn direct_music(
+    mut director: ResMut<MusicDirector>,
+    mut instances: ResMut<Assets<AudioInstance>>,
+    orc_query: Query<(&OrcEnemy, &OrcPerception, Option<&Boss>)>,
+    player_query: Query<&Health, With<Player>>,
+    time: Res<Time>,
+) {
+    let mut engaged = 0;
+    let mut boss_fight = false;
+    for (orc, perception, boss) in orc_query.iter() {
+        if orc.state == OrcState::Dying {
+            continue;
+        }
+        if perception.sees_player || perception.last_seen.is_some() {
+            engaged += 1;
+            boss_fight |= boss.is_some();
+        }
+    }
+
+    let health = player_query
+        .get_single()
+        .map_or(1.0, |health| health.health / PLAYER_MAX_HEALTH);
+
+    let mut combat = (engaged as f32 / FULL_COMBAT_ORCS).min(1.0);
+    if engaged > 0 && health < LOW_HEALTH {
+        combat = 1.0;
+    }
+
+    // Hold the current mix a moment so it doesn't flap when an orc loses sight
+    if engaged > 0 {
+        director.linger = COMBAT_LINGER;
+    } else if director.linger > 0.0 {
+        director.linger -= time.delta_secs();
+        return;
+    }
+
+    for (layer, handle, volume) in director.layers.iter_mut() {
+        let target = match (*layer, boss_fight) {
+            (MusicLayer::Boss, true) => 1.0,
+            (_, true) | (MusicLayer::Boss, false) => 0.0,
+            (MusicLayer::Calm, false) => 1.0 - combat,
+            (MusicLayer::Combat, false) => combat,
+        };
+        if *volume == target {
+            continue;
+        }
+        let Some(instance) = instances.get_mut(handle) else {
+            continue;
+        };
+        instance.set_volume(target as f64 * MUSIC_VOLUME, AudioTween::linear(CROSSFADE));
+        *volume = target;
+    }
+}
@@ -0,0 +1,289 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_kira_audio::prelude::*;
+
+use crate::animation::AnimationEvent;
+use crate::audio::{SfxChannel, UiChannel};
+use crate::camera::SmoothCamera;
+use crate::loading::{AppState, RequiredAssets};
+use crate::player_code::{Invulnerable, Player, PlayerDamageEvent};
+use crate::shield::ShieldEvent;
+use crate::spell::{SpellCastEvent, SpellStack, SpellType};
+use crate::ui_orc_counter::OrcDeathEvent;
+
+/// Every sound effect the game can play
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Sfx {
+    FireballCast,
+    BlinkCast,
+    ShieldUp,
+    ShieldBreak,
+    SpellFizzle,
+    FireballImpact,
+    OrcGrunt,
+    OrcDeath,
+    PlayerHurt,
+    SpellbookOpen,
+    SpellInputOpen,
+    SpellInputClose,
+    SpellKey,
+}
+
+impl Sfx {
+    const ALL: [Sfx; 13] = [
+        Sfx::FireballCast,
+        Sfx::BlinkCast,
+        Sfx::ShieldUp,
+        Sfx::ShieldBreak,
+        Sfx::SpellFizzle,
+        Sfx::FireballImpact,
+        Sfx::OrcGrunt,
+        Sfx::OrcDeath,
+        Sfx::PlayerHurt,
+        Sfx::SpellbookOpen,
+        Sfx::SpellInputOpen,
+        Sfx::SpellInputClose,
+        Sfx::SpellKey,
+    ];
+
+    fn path(self) -> &'static str {
+        match self {
+            Sfx::FireballCast => "audio/sfx/fireball_cast.ogg",
+            Sfx::BlinkCast => "audio/sfx/blink.ogg",
+            Sfx::ShieldUp => "audio/sfx/shield_up.ogg",
+            Sfx::ShieldBreak => "audio/sfx/shield_break.ogg",
+            Sfx::SpellFizzle => "audio/sfx/spell_fizzle.ogg",
+            Sfx::FireballImpact => "audio/sfx/fireball_impact.ogg",
+            Sfx::OrcGrunt => "audio/sfx/orc_grunt.ogg",
+            Sfx::OrcDeath => "audio/sfx/orc_death.ogg",
+            Sfx::PlayerHurt => "audio/sfx/player_hurt.ogg",
+            Sfx::SpellbookOpen => "audio/ui/spellbook_open.ogg",
+            Sfx::SpellInputOpen => "audio/ui/spell_input_open.ogg",
+            Sfx::SpellInputClose => "audio/ui/spell_input_close.ogg",
+            Sfx::SpellKey => "audio/ui/spell_key.ogg",
+        }
+    }
+
+    fn is_ui(self) -> bool {
+        matches!(
+            self,
+            Sfx::SpellbookOpen | Sfx::SpellInputOpen | Sfx::SpellInputClose | Sfx::SpellKey
+        )
+    }
+}
+
+/// Request to play a sound effect. Gameplay events are mapped to these, any
+/// system can also send one directly.
+#[derive(Event, Clone, Copy)]
+pub struct PlaySfx {
+    pub sfx: Sfx,
+    /// Where the sound happens. Positioned sounds are panned and fade with
+    /// the distance to the camera, the others play as they are.
+    pub position: Option<Vec2>,
+}
+
+impl PlaySfx {
+    pub fn new(sfx: Sfx) -> Self {
+        Self { sfx, position: None }
+    }
+
+    pub fn at(sfx: Sfx, position: Vec2) -> Self {
+        Self { sfx, position: Some(position) }
+    }
+}
+
+/// Animation event name of an orc yelling as it swings
+pub const GRUNT_EVENT: &str = "grunt";
+/// World units from the camera at which positioned sounds become silent
+const SFX_HEARING_RADIUS: f32 = 900.0;
+/// Upper bound for the lifetime of an emitter, in case its clip never loads
+const SFX_EMITTER_TIMEOUT: f32 = 10.0;
+
+// Standalone entity holding one positioned sound
+#[derive(Component)]
+struct SfxEmitter {
+    timeout: Timer,
+}
+
+/// Loaded clip of every `Sfx`
+#[derive(Resource, Default)]
+pub struct SfxLibrary {
+    clips: HashMap<Sfx, Handle<AudioSource>>,
+}
+
+impl SfxLibrary {
+    pub fn get(&self, sfx: Sfx) -> Option<&Handle<AudioSource>> {
+        self.clips.get(&sfx)
+    }
+}
+
+pub(super) struct SfxPlugin;
+
+impl Plugin for SfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlaySfx>()
+            .init_resource::<SfxLibrary>()
+            .add_plugins(SpatialAudioPlugin)
+            .add_systems(Startup, load_sfx)
+            .add_systems(OnExit(AppState::Loading), drop_missing_sfx)
+            .add_systems(Update, (attach_audio_receiver, despawn_finished_emitters))
+            .add_systems(Update, (
+                (
+                    spell_sounds,
+                    shield_sounds,
+                    orc_death_sounds,
+                    orc_grunt_sounds,
+                    player_hurt_sounds,
+                    spell_input_sounds,
+                ),
+                play_sfx,
+            ).chain());
+    }
+}
+
+fn load_sfx(
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    mut library: ResMut<SfxLibrary>,
+) {
+    for sfx in Sfx::ALL {
+        library.clips.insert(sfx, required.optional(&asset_server, sfx.path()));
+    }
+}
+
+// Effects whose clip failed to load are dropped and play as silence
+fn drop_missing_sfx(required: Res<RequiredAssets>, mut library: ResMut<SfxLibrary>) {
+    library.clips.retain(|_, clip| !required.is_missing(clip));
+}
+
+// The camera is the listener, so sounds pan with what is on screen
+fn attach_audio_receiver(mut commands: Commands, camera_query: Query<Entity, Added<SmoothCamera>>) {
+    for camera in camera_query.iter() {
+        commands.entity(camera).insert(SpatialAudioReceiver);
+    }
+}
+
+fn play_sfx(
+    mut commands: Commands,
+    mut sfx_events: EventReader<PlaySfx>,
+    library: Res<SfxLibrary>,
+    sfx_channel: Res<AudioChannel<SfxChannel>>,
+    ui_channel: Res<AudioChannel<UiChannel>>,
+) {
+    for event in sfx_events.read() {
+        let Some(clip) = library.get(event.sfx) else {
+            continue;
+        };
+        if event.sfx.is_ui() {
+            ui_channel.play(clip.clone());
+            continue;
+        }
+
+        let instance = sfx_channel.play(clip.clone()).handle();
+        if let Some(position) = event.position {
+            commands.spawn((
+                SpatialAudioEmitter { instances: vec![instance] },
+                SpatialRadius { radius: SFX_HEARING_RADIUS },
+                SfxEmitter { timeout: Timer::from_seconds(SFX_EMITTER_TIMEOUT, TimerMode::Once) },
+                Transform::from_translation(position.extend(0.0)),
+                Name::new("SfxEmitter"),
+            ));
+        }
+    }
+}
+
+fn despawn_finished_emitters(
+    mut commands: Commands,
+    mut emitter_query: Query<(Entity, &SpatialAudioEmitter, &mut SfxEmitter)>,
+    instances: Res<Assets<AudioInstance>>,
+    time: Res<Time>,
+) {
+    for (entity, emitter, mut sfx_emitter) in emitter_query.iter_mut() {
+        sfx_emitter.timeout.tick(time.delta());
+        let stopped = emitter.instances.iter().all(|handle| {
+            instances
+                .get(handle)
+                .is_some_and(|instance| instance.state() == PlaybackState::Stopped)
+        });
+        if stopped || sfx_emitter.timeout.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn spell_sounds(mut spell_events: EventReader<SpellCastEvent>, mut sfx_events: EventWriter<PlaySfx>) {
+    for event in spell_events.read() {
+        let sfx = match event.spell_type {
+            SpellType::Fireball => Sfx::FireballCast,
+            SpellType::Blink => Sfx::BlinkCast,
+            SpellType::Spellbook => Sfx::SpellbookOpen,
+            SpellType::Unknown => Sfx::SpellFizzle,
+            // Shields sound once they actually come up, see shield_sounds
+            SpellType::Shield | SpellType::Exit | SpellType::Save => continue,
+        };
+        sfx_events.send(PlaySfx::new(sfx));
+    }
+}
+
+fn shield_sounds(mut shield_events: EventReader<ShieldEvent>, mut sfx_events: EventWriter<PlaySfx>) {
+    for event in shield_events.read() {
+        let sfx = match event {
+            ShieldEvent::Activated => Sfx::ShieldUp,
+            ShieldEvent::Depleted => Sfx::ShieldBreak,
+        };
+        sfx_events.send(PlaySfx::new(sfx));
+    }
+}
+
+fn orc_death_sounds(mut death_events: EventReader<OrcDeathEvent>, mut sfx_events: EventWriter<PlaySfx>) {
+    for event in death_events.read() {
+        sfx_events.send(PlaySfx::at(Sfx::OrcDeath, event.position));
+    }
+}
+
+fn orc_grunt_sounds(
+    mut animation_events: EventReader<AnimationEvent>,
+    transform_query: Query<&Transform>,
+    mut sfx_events: EventWriter<PlaySfx>,
+) {
+    for event in animation_events.read() {
+        if event.name != GRUNT_EVENT {
+            continue;
+        }
+        if let Ok(transform) = transform_query.get(event.entity) {
+            sfx_events.send(PlaySfx::at(Sfx::OrcGrunt, transform.translation.truncate()));
+        }
+    }
+}
+
+fn player_hurt_sounds(
+    mut damage_events: EventReader<PlayerDamageEvent>,
+    invulnerable_query: Query<(), (With<Player>, With<Invulnerable>)>,
+    mut sfx_events: EventWriter<PlaySfx>,
+) {
+    // Hits during the invulnerability period are ignored, and so is their sound
+    let ignored = !invulnerable_query.is_empty();
+    for _ in damage_events.read() {
+        if !ignored {
+            sfx_events.send(PlaySfx::new(Sfx::PlayerHurt));
+        }
+    }
+}
+
+// The spell prompt has no events of its own, so compare against last frame
+fn spell_input_sounds(
+    spell_stack: Res<SpellStack>,
+    mut last: Local<(bool, usize)>,
+    mut sfx_events: EventWriter<PlaySfx>,
+) {
+    let active = spell_stack.is_active();
+    let typed = spell_stack.as_string().chars().count();
+    let (was_active, last_typed) = *last;
+    *last = (active, typed);
+
+    if active != was_active {
+        sfx_events.send(PlaySfx::new(if active { Sfx::SpellInputOpen } else { Sfx::SpellInputClose }));
+    } else if active && typed != last_typed {
+        sfx_events.send(PlaySfx::new(Sfx::SpellKey));
+    }
+}
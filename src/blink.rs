@@ -1,17 +1,22 @@
 use bevy::prelude::*;
 
-use bevy::time::{Time, Real};
+use bevy::time::Time;
 
 use crate::spell::{SpellType, SpellCastEvent};
 use crate::player_code::Player;
 use crate::player_movement::FacingDirection;
 use crate::animation::AnimationConfig;
+use crate::lighting::spawn_light_flash;
+use crate::particles::{spawn_emitter, ParticleEmitter};
+use crate::spell_assets::SpellAssets;
 
 pub const BLINK_DISTANCE: f32 = 750.0;
 pub const BLINK_ANIMATION_FIRST_INDEX: usize = 0;
 pub const BLINK_ANIMATION_LAST_INDEX: usize = 5;
 pub const BLINK_ANIMATION_FPS: u8 = 15;
 pub const BLINK_PHASE_DURATION: f32 = 0.3; // Duration for each phase in seconds
+const BLINK_FLASH_COLOR: Color = Color::srgb(0.7, 0.45, 1.0);
+const BLINK_FLASH_RADIUS: f32 = 250.0;
 
 // Component to mark when a blink animation is in progress
 #[derive(Component)]
@@ -37,9 +42,9 @@ pub struct BlinkPlugin;
 impl Plugin for BlinkPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Update, (
-            handle_blink_casting,
-            update_blink_animation,
-        ));
+                handle_blink_casting,
+                update_blink_animation,
+            ));
     }
 }
 
@@ -54,8 +59,7 @@ fn handle_blink_casting(
         &mut Sprite,
         Option<&AnimationConfig>
     ), With<Player>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    spell_assets: Res<SpellAssets>,
 ) {
     for event in spell_events.read() {
         if let SpellType::Blink = event.spell_type {
@@ -84,20 +88,14 @@ fn handle_blink_casting(
                     original_animation_config: prev_animation_config.cloned(),
                 };
                 
-                let blink_texture = asset_server.load("spells/10.png"); 
-
-                // Create a texture atlas layout for the 32x32 blink sprite
-                let layout = TextureAtlasLayout::from_grid(UVec2::new(32, 32), 3, 2, None, None);
-                let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
                 // Save previous texture information to restore later
                 let prev_texture = sprite.image.clone();
                 let prev_atlas = sprite.texture_atlas.clone();
 
                 // Update player sprite to use blink animation
-                sprite.image = blink_texture;
+                sprite.image = spell_assets.blink_texture.clone();
                 sprite.texture_atlas = Some(TextureAtlas {
-                    layout: texture_atlas_layout,
+                    layout: spell_assets.blink_layout.clone(),
                     index: BLINK_ANIMATION_FIRST_INDEX,
                 });
 
@@ -113,6 +111,15 @@ fn handle_blink_casting(
                         atlas: prev_atlas,
                     });
 
+                spawn_light_flash(
+                    &mut commands,
+                    player_transform.translation.truncate(),
+                    BLINK_FLASH_COLOR,
+                    BLINK_FLASH_RADIUS,
+                    BLINK_PHASE_DURATION,
+                );
+                spawn_emitter(&mut commands, ParticleEmitter::blink_sparkles(), player_transform.translation);
+
                 println!("Blink spell cast! Target position: {:?}", target_position);
             }
         }
@@ -136,7 +143,7 @@ fn update_blink_animation(
         &mut Sprite,
         &PreviousSprite
     )>,
-    time: Res<Time<Real>>,
+    time: Res<Time>,
 ) {
     for (entity, mut transform, mut blink_effect, mut sprite, prev_sprite) in player_query.iter_mut() {
         // Update the timer
@@ -153,6 +160,14 @@ fn update_blink_animation(
             BlinkPhase::Moving => {
                 // Second phase: instantly move player to new position
                 transform.translation = blink_effect.target_position;
+                spawn_light_flash(
+                    &mut commands,
+                    blink_effect.target_position.truncate(),
+                    BLINK_FLASH_COLOR,
+                    BLINK_FLASH_RADIUS,
+                    BLINK_PHASE_DURATION * 2.0,
+                );
+                spawn_emitter(&mut commands, ParticleEmitter::blink_sparkles(), blink_effect.target_position);
                 blink_effect.phase = BlinkPhase::Reappearing;
                 blink_effect.timer.reset();
             }
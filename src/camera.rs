@@ -1,28 +1,102 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
 use bevy::prelude::*;
+use crate::level::LevelBounds;
 use crate::player_code::Player;
 
 /// How quickly the camera should snap to the desired player location
 const CAMERA_DECAY_RATE: f32 = 2.0;
+/// How quickly the projection follows a zoom change
+const ZOOM_DECAY_RATE: f32 = 10.0;
 
-#[derive(Component)]
-pub struct SmoothCamera;
+/// Largest shake offset in world units, reached at full trauma
+const MAX_SHAKE_OFFSET: f32 = 24.0;
+/// Largest shake rotation in radians, reached at full trauma
+const MAX_SHAKE_ANGLE: f32 = 0.04;
+/// Trauma lost per second
+const TRAUMA_DECAY: f32 = 1.5;
+const SHAKE_FREQUENCY: f32 = 25.0;
+
+#[derive(Component, Default)]
+pub struct SmoothCamera {
+    /// Point the camera is centered on, before screen shake is applied
+    pub focus: Vec2,
+}
+
+/// Trauma in [0, 1], the shake grows with its square so small hits stay subtle
+#[derive(Component, Default)]
+pub struct ScreenShake {
+    pub trauma: f32,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).min(1.0);
+    }
+}
+
+/// Shake the camera, e.g. `ScreenShakeEvent { trauma: 0.3 }` for a hit
+#[derive(Event)]
+pub struct ScreenShakeEvent {
+    pub trauma: f32,
+}
+
+/// Player-facing camera options
+#[derive(Resource)]
+pub struct CameraSettings {
+    /// Projection scale, above 1 shows more of the world
+    pub zoom: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    /// Zoom change per mouse wheel notch
+    pub zoom_step: f32,
+    pub screen_shake: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            min_zoom: 0.5,
+            max_zoom: 2.0,
+            zoom_step: 0.1,
+            screen_shake: true,
+        }
+    }
+}
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ScreenShakeEvent>()
+            .init_resource::<CameraSettings>()
+            .add_systems(Update, (
+                zoom_with_mouse_wheel,
+                apply_zoom.after(zoom_with_mouse_wheel),
+                update_camera.after(apply_zoom),
+                apply_screen_shake.after(update_camera),
+            ));
+    }
+}
 
 pub fn setup_camera(mut commands: Commands) {
     commands.spawn((
         Camera2d::default(),
-        SmoothCamera,
+        SmoothCamera::default(),
+        ScreenShake::default(),
     ));
 }
 
 /// Update the camera position by smoothly tracking the player
 pub fn update_camera(
-    mut camera_query: Query<&mut Transform, (With<SmoothCamera>, Without<Player>)>,
+    mut camera_query: Query<(&mut SmoothCamera, &mut Transform, &OrthographicProjection), Without<Player>>,
     player_query: Query<&Transform, (With<Player>, Without<SmoothCamera>)>,
+    level_bounds: Option<Res<LevelBounds>>,
     time: Res<Time>,
 ) {
-    // Get single camera transform or return early if none exists
-    let mut camera_transform = match camera_query.get_single_mut() {
-        Ok(transform) => transform,
+    // Get single camera or return early if none exists
+    let (mut camera, mut camera_transform, projection) = match camera_query.get_single_mut() {
+        Ok(camera) => camera,
         Err(_) => return,
     };
 
@@ -32,12 +106,92 @@ pub fn update_camera(
         Err(_) => return,
     };
 
-    let Vec3 { x, y, .. } = player_transform.translation;
-    let target_position = Vec3::new(x, y, camera_transform.translation.z);
-
     // Apply smooth tracking using lerp
-    camera_transform.translation = camera_transform.translation.lerp(
-        target_position,
+    camera.focus = camera.focus.lerp(
+        player_transform.translation.truncate(),
         CAMERA_DECAY_RATE * time.delta_secs()
     );
-}
\ No newline at end of file
+
+    // Never show anything outside the current room
+    if let Some(bounds) = level_bounds {
+        camera.focus = clamp_to_bounds(camera.focus, bounds.rect, projection.area.half_size());
+    }
+
+    camera_transform.translation = camera.focus.extend(camera_transform.translation.z);
+}
+
+// Keep the whole view inside `bounds`, centering it when the room is
+// smaller than the view
+fn clamp_to_bounds(focus: Vec2, bounds: Rect, half_view: Vec2) -> Vec2 {
+    let clamp_axis = |value: f32, min: f32, max: f32, half: f32| {
+        if max - min <= half * 2.0 {
+            (min + max) / 2.0
+        } else {
+            value.clamp(min + half, max - half)
+        }
+    };
+    Vec2::new(
+        clamp_axis(focus.x, bounds.min.x, bounds.max.x, half_view.x),
+        clamp_axis(focus.y, bounds.min.y, bounds.max.y, half_view.y),
+    )
+}
+
+fn zoom_with_mouse_wheel(
+    mut wheel_events: EventReader<MouseWheel>,
+    mut settings: ResMut<CameraSettings>,
+) {
+    for event in wheel_events.read() {
+        // Touchpads report pixels, mice report lines
+        let notches = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        };
+        // Scrolling up zooms in
+        settings.zoom = (settings.zoom - notches * settings.zoom_step)
+            .clamp(settings.min_zoom, settings.max_zoom);
+    }
+}
+
+fn apply_zoom(
+    settings: Res<CameraSettings>,
+    mut projection_query: Query<&mut OrthographicProjection, With<SmoothCamera>>,
+    time: Res<Time>,
+) {
+    if let Ok(mut projection) = projection_query.get_single_mut() {
+        let step = (ZOOM_DECAY_RATE * time.delta_secs()).min(1.0);
+        projection.scale += (settings.zoom - projection.scale) * step;
+    }
+}
+
+fn apply_screen_shake(
+    mut shake_events: EventReader<ScreenShakeEvent>,
+    mut camera_query: Query<(&SmoothCamera, &mut ScreenShake, &mut Transform)>,
+    settings: Res<CameraSettings>,
+    time: Res<Time>,
+) {
+    let Ok((camera, mut shake, mut transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    for event in shake_events.read() {
+        if settings.screen_shake {
+            shake.add_trauma(event.trauma);
+        }
+    }
+
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+    let amount = shake.trauma * shake.trauma;
+
+    // Smooth noise so the camera wobbles instead of teleporting around
+    let t = time.elapsed_secs() * SHAKE_FREQUENCY;
+    let offset = Vec2::new(shake_noise(t, 0.0), shake_noise(t, 1.7)) * MAX_SHAKE_OFFSET * amount;
+
+    // Offset from the focus point, so the shake never drifts the camera
+    transform.translation = (camera.focus + offset).extend(transform.translation.z);
+    transform.rotation = Quat::from_rotation_z(shake_noise(t, 3.1) * MAX_SHAKE_ANGLE * amount);
+}
+
+// Sum of two out of phase sines, stays within [-1, 1]
+fn shake_noise(t: f32, seed: f32) -> f32 {
+    (t + seed).sin() * 0.6 + (t * 1.73 + seed * 2.1).sin() * 0.4
+}
@@ -1,6 +1,5 @@
 use bevy::prelude::*;
 use bevy::time::Time;
-use bevy::time::Real;
 use bevy_rapier2d::prelude::*;
 
 
@@ -12,6 +11,14 @@ use crate::orc::{OrcEnemy, OrcState};
 use crate::orc::collision::{HurtHitbox, AttackHitbox};
 use crate::player_code::Health;
 use crate::ui_orc_counter::OrcDeathEvent;
+use crate::audio::{PlaySfx, Sfx};
+use crate::level::LevelEntity;
+use crate::spell_assets::SpellAssets;
+use crate::camera::ScreenShakeEvent;
+use crate::player_code::PLAYER_FOOT_OFFSET;
+use crate::ysort::YSort;
+use crate::lighting::Light2d;
+use crate::particles::{spawn_emitter, ParticleEmitter};
 
 pub const FIREBALL_SPEED: f32 = 200.0;
 pub const FIREBALL_LIFETIME: f32 = 5.0;
@@ -19,6 +26,7 @@ pub const FIREBALL_DAMAGE: f32 = 10.0;
 pub const FIREBALL_FIRST_INDEX: usize = 0;
 pub const FIREBALL_LAST_INDEX: usize = 11;
 pub const FIREBALL_FPS: u8 = 12;
+const FIREBALL_IMPACT_TRAUMA: f32 = 0.25;
 
 //Death timer
 #[derive(Component)]
@@ -111,8 +119,7 @@ fn handle_fireball_casting(
     mut commands: Commands,
     mut spell_events: EventReader<SpellCastEvent>,
     player_query: Query<(&Transform, &FacingDirection), With<Player>>,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    spell_assets: Res<SpellAssets>,
 ) {
     for event in spell_events.read() {
         if let SpellType::Fireball = event.spell_type {
@@ -129,11 +136,6 @@ fn handle_fireball_casting(
                 let offset = direction * 30.0; // Offset to place fireball in front of player
                 let spawn_position = player_transform.translation + Vec3::new(offset.x, offset.y, 0.0);
 
-                // Load texture and create texture atlas
-                let fireball_texture = asset_server.load("spells/03.png");
-                let layout = TextureAtlasLayout::from_grid(UVec2::new(32, 32), 6, 2, None, None);
-                let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
                 // Create animation configuration
                 let fireball_animation = AnimationConfig::new(
                     FIREBALL_FIRST_INDEX,
@@ -144,9 +146,9 @@ fn handle_fireball_casting(
                 // Spawn fireball entity - removed the .id() call since we don't use the return value
                 commands.spawn((
                     Sprite {
-                        image: fireball_texture.clone(),
+                        image: spell_assets.fireball_texture.clone(),
                         texture_atlas: Some(TextureAtlas {
-                            layout: texture_atlas_layout,
+                            layout: spell_assets.fireball_layout.clone(),
                             index: FIREBALL_FIRST_INDEX,
                         }),
                         flip_x: !facing.facing_right,
@@ -154,8 +156,19 @@ fn handle_fireball_casting(
                     },
                     Transform::from_translation(spawn_position)
                         .with_scale(Vec3::splat(2.0)), // Size of the fireball
+                    // Sorted by the ground under it, it flies at the caster's height
+                    YSort { offset: PLAYER_FOOT_OFFSET },
                     Fireball::new(direction, FIREBALL_DAMAGE),
+                    Light2d {
+                        color: Color::srgb(1.0, 0.55, 0.2),
+                        radius: 180.0,
+                        intensity: 1.0,
+                    },
                     fireball_animation,
+                    ParticleEmitter::fireball_trail(
+                        spell_assets.fireball_texture.clone(),
+                        spell_assets.fireball_layout.clone(),
+                    ),
 
                     // Add physics components for collision detection
                     Collider::ball(8.0),
@@ -163,6 +176,7 @@ fn handle_fireball_casting(
                     ActiveEvents::COLLISION_EVENTS,
 
                     Name::new("Fireball"),
+                    LevelEntity,
                 ));
             }
         }
@@ -172,7 +186,7 @@ fn handle_fireball_casting(
 // System to update fireball positions
 fn update_fireballs(
     mut fireball_query: Query<(&mut Transform, &Fireball)>,
-    time: Res<Time<Real>>,
+    time: Res<Time>,
 ) {
     for (mut transform, fireball) in fireball_query.iter_mut() {
         if !fireball.is_disabled() && !fireball.marked_for_despawn {
@@ -187,12 +201,14 @@ fn update_fireballs(
 fn handle_fireball_collisions(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
-    mut fireball_query: Query<(Entity, &mut Fireball)>,
+    mut fireball_query: Query<(Entity, &mut Fireball, &Transform)>,
     hurtbox_query: Query<(Entity, &HurtHitbox)>,
     attack_hitbox_query: Query<(Entity, &crate::orc::collision::AttackHitbox)>,
-    mut orc_query: Query<(&mut OrcEnemy, &mut Health)>,
+    mut orc_query: Query<(&mut OrcEnemy, &mut Health, &Transform)>,
     mut despawn_events: EventWriter<FireballDespawnEvent>,
     mut orc_death_events: EventWriter<OrcDeathEvent>,
+    mut shake_events: EventWriter<ScreenShakeEvent>,
+    mut sfx_events: EventWriter<PlaySfx>,
 ) {
     // iterate all new collision events
     for event in collision_events.read() {
@@ -213,7 +229,7 @@ fn handle_fireball_collisions(
             }
 
             // grab &mut Fireball
-            if let Ok((_, mut fb)) = fireball_query.get_mut(fb_ent) {
+            if let Ok((_, mut fb, fb_transform)) = fireball_query.get_mut(fb_ent) {
                 // skip if already disabled by some other logic
                 if fb.is_disabled() || fb.marked_for_despawn {
                     continue;
@@ -224,7 +240,7 @@ fn handle_fireball_collisions(
                     let orc_ent = hurtbox.owner;
 
                     // damage the orc
-                    if let Ok((mut orc, mut health)) = orc_query.get_mut(orc_ent) {
+                    if let Ok((mut orc, mut health, orc_transform)) = orc_query.get_mut(orc_ent) {
                         health.health -= fb.damage;
 
                         if health.health <= 0.0 {
@@ -232,7 +248,11 @@ fn handle_fireball_collisions(
                             orc.state = OrcState::Dying;
 
                             // Send death event for the counter
-                            orc_death_events.send(OrcDeathEvent);
+                            orc_death_events.send(OrcDeathEvent {
+                                entity: orc_ent,
+                                position: orc_transform.translation.truncate(),
+                            });
+                            spawn_emitter(&mut commands, ParticleEmitter::death_dust(), orc_transform.translation);
 
                             // lock its position and start your death timer
                             commands.entity(orc_ent)
@@ -263,6 +283,9 @@ fn handle_fireball_collisions(
                         }
                     }
 
+                    shake_events.send(ScreenShakeEvent { trauma: FIREBALL_IMPACT_TRAUMA });
+                    sfx_events.send(PlaySfx::at(Sfx::FireballImpact, fb_transform.translation.truncate()));
+
                     // Mark the fireball for despawn and send an event
                     fb.mark_for_despawn();
                     fb.disable();
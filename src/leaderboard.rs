@@ -0,0 +1,184 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::loading::AppState;
+use crate::spell::{SpellCastEvent, SpellType};
+use crate::storage;
+use crate::ui_orc_counter::OrcDeathCounter;
+
+/// Runs kept on the leaderboard
+const LEADERBOARD_SIZE: usize = 10;
+
+/// Statistics of the run in progress
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Seconds spent in game, pauses and menus excluded
+    pub survival_secs: f32,
+    pub spells_cast: u32,
+    pub misspells: u32,
+    /// Letters of every cast, and of the casts that were real spells
+    pub letters_typed: u32,
+    pub letters_correct: u32,
+}
+
+impl RunStats {
+    /// Share of the typed letters that ended up in a real spell, from 0 to 1
+    pub fn accuracy(&self) -> f32 {
+        if self.letters_typed == 0 {
+            return 1.0;
+        }
+        self.letters_correct as f32 / self.letters_typed as f32
+    }
+}
+
+/// A finished run on the leaderboard
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub kills: u32,
+    pub survival_secs: f32,
+    pub spells_cast: u32,
+    pub misspells: u32,
+    pub accuracy: f32,
+}
+
+impl RunRecord {
+    fn describe(&self) -> String {
+        let secs = self.survival_secs as u32;
+        format!(
+            "{:>3} kills  {:>2}:{:02}  {:>3} spells  {:>3} misspelled  {:>3.0}% accuracy",
+            self.kills,
+            secs / 60,
+            secs % 60,
+            self.spells_cast,
+            self.misspells,
+            self.accuracy * 100.0,
+        )
+    }
+}
+
+/// Best runs first, most kills and then longest survival
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub runs: Vec<RunRecord>,
+    /// Position of the run that just ended, if it made it onto the board
+    #[serde(skip)]
+    pub last_run: Option<usize>,
+}
+
+impl Leaderboard {
+    fn path() -> PathBuf {
+        storage::data_dir().join("leaderboard.ron")
+    }
+
+    fn read() -> Self {
+        let path = Self::path();
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Self::default(); // No run finished yet
+        };
+        ron::from_str(&text).unwrap_or_else(|err| {
+            error!("Could not read leaderboard {}: {}", path.display(), err);
+            Self::default()
+        })
+    }
+
+    fn write(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = ron::ser::to_string_pretty(self, PrettyConfig::default()).map_err(io::Error::other)?;
+        fs::write(path, text)
+    }
+
+    fn insert(&mut self, record: RunRecord) {
+        let position = self
+            .runs
+            .iter()
+            .position(|run| (record.kills, record.survival_secs) > (run.kills, run.survival_secs))
+            .unwrap_or(self.runs.len());
+        self.runs.insert(position, record);
+        self.runs.truncate(LEADERBOARD_SIZE);
+        self.last_run = (position < LEADERBOARD_SIZE).then_some(position);
+    }
+
+    /// Add the leaderboard as text lines under `parent`, the last run highlighted
+    pub fn spawn_lines(&self, parent: &mut ChildBuilder, font: &Handle<Font>, rows: usize) {
+        if self.runs.is_empty() {
+            return;
+        }
+        let text_font = TextFont {
+            font: font.clone(),
+            font_size: 18.0,
+            ..default()
+        };
+
+        parent.spawn((
+            Text::new("Best runs"),
+            TextFont {
+                font_size: 24.0,
+                ..text_font.clone()
+            },
+        ));
+        for (index, run) in self.runs.iter().take(rows).enumerate() {
+            let color = if self.last_run == Some(index) {
+                Color::srgb(1.0, 0.85, 0.3)
+            } else {
+                Color::srgb(0.8, 0.8, 0.8)
+            };
+            parent.spawn((
+                Text::new(format!("{:>2}. {}", index + 1, run.describe())),
+                text_font.clone(),
+                TextColor(color),
+            ));
+        }
+    }
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .insert_resource(Leaderboard::read())
+            .add_systems(OnEnter(AppState::GameOver), record_run)
+            .add_systems(Update, (
+                track_survival_time.run_if(in_state(AppState::InGame)),
+                track_spells,
+            ));
+    }
+}
+
+fn track_survival_time(mut stats: ResMut<RunStats>, time: Res<Time>) {
+    stats.survival_secs += time.delta_secs();
+}
+
+fn track_spells(mut spell_events: EventReader<SpellCastEvent>, mut stats: ResMut<RunStats>) {
+    for event in spell_events.read() {
+        let letters = event.spell_name.chars().count() as u32;
+        stats.spells_cast += 1;
+        stats.letters_typed += letters;
+        if event.spell_type == SpellType::Unknown {
+            stats.misspells += 1;
+        } else {
+            stats.letters_correct += letters;
+        }
+    }
+}
+
+pub fn record_run(stats: Res<RunStats>, kill_count: Res<OrcDeathCounter>, mut leaderboard: ResMut<Leaderboard>) {
+    leaderboard.insert(RunRecord {
+        kills: kill_count.count,
+        survival_secs: stats.survival_secs,
+        spells_cast: stats.spells_cast,
+        misspells: stats.misspells,
+        accuracy: stats.accuracy(),
+    });
+    if let Err(err) = leaderboard.write() {
+        error!("Could not save leaderboard: {}", err);
+    }
+}
@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy::utils::HashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::level::spawn::{merge_solid_cells, MapTransform, LEVEL_LAYER_Z};
+use crate::level::{LevelEntity, OrcSpawnPoint, PlayerSpawn, TriggerZone, DUNGEON_LEVEL};
+use crate::lighting::{spawn_torch, LevelLighting};
+use crate::pickup::{spawn_pickup, PickupKind};
+use crate::world::{self, Background};
+
+// Grid size of a floor, in tiles
+const DUNGEON_WIDTH: u32 = 64;
+const DUNGEON_HEIGHT: u32 = 48;
+const TILE_SIZE: u32 = 16;
+const DUNGEON_SCALE: f32 = 2.0;
+
+const MAX_ROOMS: usize = 9;
+const ROOM_ATTEMPTS: u32 = 200;
+const MIN_ROOM_SIZE: u32 = 6;
+const MAX_ROOM_SIZE: u32 = 13;
+const CORRIDOR_WIDTH: u32 = 2;
+
+const DUNGEON_DARKNESS: f32 = 0.85;
+
+const POTION_CHANCE: f64 = 0.35;
+const CHARM_CHANCE: f64 = 0.15;
+
+// Tile indices in dungeon_tiles.png
+const TILE_FLOOR: usize = 0;
+const TILE_FLOOR_WORN: usize = 1;
+const TILE_WALL_FACE: usize = 2;
+const TILE_WALL_TOP: usize = 3;
+const TILE_STAIRS: usize = 4;
+
+/// Texture shared by every generated floor
+#[derive(Resource)]
+pub struct DungeonTiles {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+/// Layout of one generated floor, in tile coordinates (y down, like Tiled)
+pub struct Dungeon {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major, true where the player can walk
+    pub floor: Vec<bool>,
+    pub rooms: Vec<URect>,
+    pub player_spawn: UVec2,
+    pub exit: UVec2,
+    pub orc_spawns: Vec<UVec2>,
+    pub pickups: Vec<(PickupKind, UVec2)>,
+    /// Wall cells holding a torch
+    pub torches: Vec<UVec2>,
+}
+
+impl Dungeon {
+    pub fn is_floor(&self, x: i32, y: i32) -> bool {
+        x >= 0
+            && y >= 0
+            && (x as u32) < self.width
+            && (y as u32) < self.height
+            && self.floor[(y as u32 * self.width + x as u32) as usize]
+    }
+
+    fn carve(&mut self, rect: URect) {
+        for y in rect.min.y..rect.max.y {
+            for x in rect.min.x..rect.max.x {
+                self.floor[(y * self.width + x) as usize] = true;
+            }
+        }
+    }
+}
+
+/// Every floor gets its own seed, derived from the run seed
+pub fn floor_seed(run_seed: u64, floor: u32) -> u64 {
+    run_seed ^ (floor as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Generate a floor. The same seed and depth always give the same dungeon.
+pub fn generate(seed: u64, depth: u32) -> Dungeon {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut dungeon = Dungeon {
+        width: DUNGEON_WIDTH,
+        height: DUNGEON_HEIGHT,
+        floor: vec![false; (DUNGEON_WIDTH * DUNGEON_HEIGHT) as usize],
+        rooms: Vec::new(),
+        player_spawn: UVec2::ZERO,
+        exit: UVec2::ZERO,
+        orc_spawns: Vec::new(),
+        pickups: Vec::new(),
+        torches: Vec::new(),
+    };
+
+    // Scatter rooms, keeping at least two tiles of wall between them
+    for _ in 0..ROOM_ATTEMPTS {
+        if dungeon.rooms.len() == MAX_ROOMS {
+            break;
+        }
+        let w = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
+        let h = rng.gen_range(MIN_ROOM_SIZE..=MAX_ROOM_SIZE);
+        // Leave room for the wall tops drawn above the top row
+        let x = rng.gen_range(1..DUNGEON_WIDTH - w - 1);
+        let y = rng.gen_range(3..DUNGEON_HEIGHT - h - 1);
+        let room = URect::new(x, y, x + w, y + h);
+
+        let overlaps = dungeon.rooms.iter().any(|other| !other.inflate(2).intersect(room).is_empty());
+        if !overlaps {
+            dungeon.rooms.push(room);
+        }
+    }
+
+    // Chaining rooms from west to east keeps every room reachable
+    dungeon.rooms.sort_by_key(|room| room.center().x);
+    for room in dungeon.rooms.clone() {
+        dungeon.carve(room);
+    }
+    for pair in dungeon.rooms.clone().windows(2) {
+        let (from, to) = (pair[0].center(), pair[1].center());
+        // L-shaped corridor, randomly horizontal or vertical first
+        let corner = if rng.gen_bool(0.5) {
+            UVec2::new(to.x, from.y)
+        } else {
+            UVec2::new(from.x, to.y)
+        };
+        dungeon.carve(corridor(from, corner));
+        dungeon.carve(corridor(corner, to));
+    }
+
+    let (Some(first), Some(last)) = (dungeon.rooms.first().copied(), dungeon.rooms.last().copied()) else {
+        return dungeon;
+    };
+    dungeon.player_spawn = first.center();
+    dungeon.exit = last.center();
+
+    // Deeper floors are more crowded. The first room is left safe.
+    let max_orcs = 2 + depth.min(3);
+    for room in dungeon.rooms.iter().skip(1) {
+        let inner = room.inflate(-1);
+        for _ in 0..rng.gen_range(1..=max_orcs) {
+            let cell = UVec2::new(
+                rng.gen_range(inner.min.x..inner.max.x),
+                rng.gen_range(inner.min.y..inner.max.y),
+            );
+            dungeon.orc_spawns.push(cell);
+        }
+    }
+
+    // One torch on the north wall of every room, unless a corridor opens there
+    for room in &dungeon.rooms {
+        let cell = UVec2::new(room.center().x, room.min.y - 1);
+        if !dungeon.is_floor(cell.x as i32, cell.y as i32) {
+            dungeon.torches.push(cell);
+        }
+    }
+
+    for room in &dungeon.rooms {
+        let inner = room.inflate(-1);
+        let cell = UVec2::new(
+            rng.gen_range(inner.min.x..inner.max.x),
+            rng.gen_range(inner.min.y..inner.max.y),
+        );
+        let roll = rng.gen_range(0.0..1.0);
+        if roll < POTION_CHANCE {
+            dungeon.pickups.push((PickupKind::HealthPotion, cell));
+        } else if roll < POTION_CHANCE + CHARM_CHANCE {
+            dungeon.pickups.push((PickupKind::ShieldCharm, cell));
+        }
+    }
+
+    dungeon
+}
+
+fn corridor(a: UVec2, b: UVec2) -> URect {
+    let min = a.min(b);
+    let max = a.max(b) + UVec2::splat(CORRIDOR_WIDTH);
+    URect::from_corners(min, max)
+}
+
+/// Ambient light of every generated floor
+pub fn lighting() -> LevelLighting {
+    LevelLighting {
+        darkness: DUNGEON_DARKNESS,
+        color: Color::srgb(0.02, 0.02, 0.06),
+    }
+}
+
+/// Spawn a generated floor as level entities, returning the world space
+/// area it covers
+pub fn spawn_dungeon(commands: &mut Commands, dungeon: &Dungeon, tiles: &DungeonTiles) -> Rect {
+    let tile_size = Vec2::splat(TILE_SIZE as f32);
+    let transform = MapTransform {
+        scale: DUNGEON_SCALE,
+        size: UVec2::new(dungeon.width, dungeon.height).as_vec2() * tile_size,
+    };
+    let cell_to_world = |cell: UVec2| transform.to_world((cell.as_vec2() + Vec2::splat(0.5)) * tile_size);
+
+    let spawn_tile = |commands: &mut Commands, cell: UVec2, index: usize, z: f32| {
+        commands.spawn((
+            Sprite {
+                image: tiles.image.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: tiles.layout.clone(),
+                    index,
+                }),
+                ..default()
+            },
+            Transform::from_translation(cell_to_world(cell).extend(z))
+                .with_scale(Vec3::splat(DUNGEON_SCALE)),
+            Background,
+            LevelEntity,
+        ));
+    };
+
+    // Floor tiles, and wall tiles only where they touch the floor
+    for y in 0..dungeon.height {
+        for x in 0..dungeon.width {
+            let cell = UVec2::new(x, y);
+            let (xi, yi) = (x as i32, y as i32);
+            if dungeon.is_floor(xi, yi) {
+                let index = if (x * 7 + y * 13) % 11 == 0 { TILE_FLOOR_WORN } else { TILE_FLOOR };
+                spawn_tile(commands, cell, index, LEVEL_LAYER_Z);
+                continue;
+            }
+            let touches_floor = (-1..=1).any(|dy| (-1..=1).any(|dx| dungeon.is_floor(xi + dx, yi + dy)))
+                || dungeon.is_floor(xi, yi + 2);
+            if touches_floor {
+                // Brick faces are seen from the south, everything else is wall top
+                let index = if dungeon.is_floor(xi, yi + 1) { TILE_WALL_FACE } else { TILE_WALL_TOP };
+                spawn_tile(commands, cell, index, LEVEL_LAYER_Z + 0.01);
+            }
+        }
+    }
+    spawn_tile(commands, dungeon.exit, TILE_STAIRS, LEVEL_LAYER_Z + 0.01);
+
+    // Everything that isn't floor blocks
+    let solid: Vec<bool> = dungeon.floor.iter().map(|floor| !floor).collect();
+    for rect in merge_solid_cells(dungeon.width as usize, dungeon.height as usize, &solid) {
+        let min = rect.min.as_vec2() * tile_size;
+        let max = rect.max.as_vec2() * tile_size;
+        let center = transform.to_world((min + max) / 2.0);
+        let half_extents = (max - min) / 2.0 * DUNGEON_SCALE;
+        let wall = world::spawn_wall(commands, center, half_extents);
+        commands.entity(wall).insert(LevelEntity);
+    }
+
+    commands.spawn((
+        PlayerSpawn { name: "player".to_string() },
+        Transform::from_translation(cell_to_world(dungeon.player_spawn).extend(0.0)),
+        LevelEntity,
+    ));
+    for cell in &dungeon.orc_spawns {
+        commands.spawn((
+            OrcSpawnPoint,
+            Transform::from_translation(cell_to_world(*cell).extend(0.0)),
+            LevelEntity,
+        ));
+    }
+    for cell in &dungeon.torches {
+        let torch = spawn_torch(commands, cell_to_world(*cell));
+        commands.entity(torch).insert(LevelEntity);
+    }
+    for (kind, cell) in &dungeon.pickups {
+        let pickup = spawn_pickup(commands, *kind, cell_to_world(*cell));
+        commands.entity(pickup).insert(LevelEntity);
+    }
+
+    // Stairs down to the next floor
+    let half_extents = tile_size / 2.0 * DUNGEON_SCALE;
+    commands.spawn((
+        TriggerZone {
+            name: "stairs".to_string(),
+            properties: HashMap::from_iter([("door_to".to_string(), DUNGEON_LEVEL.to_string())]),
+        },
+        Collider::cuboid(half_extents.x, half_extents.y),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+        Transform::from_translation(cell_to_world(dungeon.exit).extend(0.0)),
+        Name::new("Trigger-stairs"),
+        LevelEntity,
+    ));
+
+    Rect::from_corners(transform.to_world(Vec2::ZERO), transform.to_world(transform.size))
+}
@@ -0,0 +1,223 @@
+mod tiled;
+pub mod spawn;
+pub mod dungeon;
+pub mod parallax;
+pub mod transition;
+
+pub use tiled::{TiledMap, TiledMapLoader};
+pub use transition::LevelTransitionEvent;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use crate::camera::SmoothCamera;
+use crate::lighting::LevelLighting;
+use crate::player_code::Player;
+use crate::loading::RequiredAssets;
+use crate::rng::GameRng;
+use dungeon::DungeonTiles;
+
+/// Level loaded when the game starts
+pub const START_LEVEL: &str = "levels/library.tmx";
+const DEFAULT_SPAWN: &str = "player";
+
+/// Door target meaning "the next generated dungeon floor"
+pub const DUNGEON_LEVEL: &str = "dungeon";
+
+/// Start on a generated floor instead of the library
+const DUNGEON_ARG: &str = "--dungeon";
+
+/// Half the width and height of the empty arena
+const ARENA_HALF_SIZE: f32 = 1000.0;
+
+/// Marker for everything that belongs to the current level
+#[derive(Component)]
+pub struct LevelEntity;
+
+/// Where the player is placed when the level starts. Doors name the
+/// spawn they lead to, otherwise the one named "player" is used.
+#[derive(Component)]
+pub struct PlayerSpawn {
+    pub name: String,
+}
+
+/// An orc is spawned here when the level starts
+#[derive(Component)]
+pub struct OrcSpawnPoint;
+
+/// Sensor area authored in the level, its meaning comes from the properties
+#[derive(Component)]
+pub struct TriggerZone {
+    pub name: String,
+    pub properties: HashMap<String, String>,
+}
+
+/// Where the layout of a level comes from
+pub enum LevelSource {
+    /// Authored in Tiled
+    Map(Handle<TiledMap>),
+    /// Generated from a seed, `floor` counting from 0
+    Dungeon { seed: u64, floor: u32 },
+    /// Open floor without walls or spawn points, for scripted scenarios
+    Arena,
+}
+
+impl LevelSource {
+    /// Resolve a door target, which is either a map path or [`DUNGEON_LEVEL`]
+    pub fn from_target(target: &str, current: &LevelSource, rng: &GameRng, asset_server: &AssetServer) -> Self {
+        if target != DUNGEON_LEVEL {
+            return LevelSource::Map(asset_server.load(target.to_string()));
+        }
+        let floor = match current {
+            LevelSource::Dungeon { floor, .. } => floor + 1,
+            LevelSource::Map(_) | LevelSource::Arena => 0,
+        };
+        LevelSource::Dungeon { seed: dungeon::floor_seed(rng.seed, floor), floor }
+    }
+
+    /// Level a new game starts on, the library unless launched with `--dungeon`
+    pub fn start(asset_server: &AssetServer, rng: &GameRng) -> Self {
+        if std::env::args().any(|arg| arg == DUNGEON_ARG) {
+            LevelSource::Dungeon { seed: dungeon::floor_seed(rng.seed, 0), floor: 0 }
+        } else {
+            LevelSource::Map(asset_server.load(START_LEVEL))
+        }
+    }
+}
+
+/// The level currently loaded, spawned once it is ready
+#[derive(Resource)]
+pub struct CurrentLevel {
+    pub source: LevelSource,
+    pub spawn_point: Option<String>,
+    pub spawned: bool,
+    /// Restored from a save, which places the player, the orcs and the pickups itself
+    pub from_save: bool,
+}
+
+/// World space area of the current room, the camera stays inside it
+#[derive(Resource)]
+pub struct LevelBounds {
+    pub rect: Rect,
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<TiledMap>()
+            .register_asset_loader(TiledMapLoader)
+            .add_plugins((transition::LevelTransitionPlugin, parallax::ParallaxPlugin))
+            .add_systems(Startup, (load_dungeon_tiles, load_start_level))
+            .add_systems(Update, (
+                spawn_level_when_loaded,
+                place_player_at_spawn.after(spawn_level_when_loaded),
+            ));
+    }
+}
+
+fn load_dungeon_tiles(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let layout = TextureAtlasLayout::from_grid(UVec2::splat(16), 5, 1, None, None);
+    commands.insert_resource(DungeonTiles {
+        image: required.image(&asset_server, "levels/dungeon_tiles.png", UVec2::new(80, 16)),
+        layout: atlas_layouts.add(layout),
+    });
+}
+
+fn load_start_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    rng: Res<GameRng>,
+    picked: Option<Res<CurrentLevel>>,
+) {
+    if picked.is_some() {
+        return; // The app picked its own level, e.g. the headless arena
+    }
+    let source = LevelSource::start(&asset_server, &rng);
+    if matches!(source, LevelSource::Map(_)) {
+        // Same handle, tracked so the game waits for the map
+        let _: Handle<TiledMap> = required.load(&asset_server, START_LEVEL);
+    }
+    commands.insert_resource(CurrentLevel {
+        source,
+        spawn_point: None,
+        spawned: false,
+        from_save: false,
+    });
+}
+
+fn spawn_level_when_loaded(
+    mut commands: Commands,
+    mut current_level: ResMut<CurrentLevel>,
+    maps: Res<Assets<TiledMap>>,
+    dungeon_tiles: Res<DungeonTiles>,
+) {
+    if current_level.spawned {
+        return;
+    }
+
+    let bounds = match &current_level.source {
+        LevelSource::Map(handle) => {
+            let Some(map) = maps.get(handle) else {
+                return; // Still loading
+            };
+            info!("Level spawned ({}x{} tiles)", map.width, map.height);
+            commands.insert_resource(spawn::map_lighting(map));
+            spawn::spawn_tiled_map(&mut commands, map, !current_level.from_save)
+        }
+        LevelSource::Dungeon { seed, floor } => {
+            let mut dungeon = dungeon::generate(*seed, *floor);
+            if current_level.from_save {
+                // The save brings back the pickups that weren't collected
+                dungeon.pickups.clear();
+            }
+            info!("Dungeon floor {} generated from seed {} ({} rooms)", floor, seed, dungeon.rooms.len());
+            commands.insert_resource(dungeon::lighting());
+            dungeon::spawn_dungeon(&mut commands, &dungeon, &dungeon_tiles)
+        }
+        LevelSource::Arena => {
+            commands.insert_resource(LevelLighting::default());
+            Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(ARENA_HALF_SIZE))
+        }
+    };
+    commands.insert_resource(LevelBounds { rect: bounds });
+    current_level.spawned = true;
+}
+
+// Move the player onto the level's spawn point, and snap the camera there
+// so it doesn't sweep across the new room
+fn place_player_at_spawn(
+    spawn_query: Query<(&PlayerSpawn, &Transform), (Added<PlayerSpawn>, Without<Player>)>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+    mut camera_query: Query<&mut SmoothCamera>,
+    current_level: Res<CurrentLevel>,
+) {
+    if current_level.from_save {
+        return;
+    }
+    let wanted = current_level.spawn_point.as_deref().unwrap_or(DEFAULT_SPAWN);
+    let spawn = spawn_query
+        .iter()
+        .find(|(spawn, _)| spawn.name == wanted)
+        .or_else(|| spawn_query.iter().next());
+
+    let Some((spawn, spawn_transform)) = spawn else {
+        return;
+    };
+    if spawn.name != wanted {
+        warn!("Spawn point '{}' not found, using '{}'", wanted, spawn.name);
+    }
+
+    if let Ok(mut player_transform) = player_query.get_single_mut() {
+        player_transform.translation.x = spawn_transform.translation.x;
+        player_transform.translation.y = spawn_transform.translation.y;
+    }
+    if let Ok(mut camera) = camera_query.get_single_mut() {
+        camera.focus = spawn_transform.translation.truncate();
+    }
+}
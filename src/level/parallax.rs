@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::camera::{self, SmoothCamera};
+
+/// A map layer scrolling at its own speed relative to the camera.
+/// A factor of 1 moves with the world, 0 stays fixed on screen.
+#[derive(Component)]
+pub struct ParallaxLayer {
+    pub factor: Vec2,
+    /// Where the camera has to be for the layer to line up with the rest of the map
+    pub origin: Vec2,
+}
+
+pub struct ParallaxPlugin;
+
+impl Plugin for ParallaxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, scroll_parallax_layers.after(camera::update_camera));
+    }
+}
+
+fn scroll_parallax_layers(
+    camera_query: Query<&SmoothCamera>,
+    mut layer_query: Query<(&ParallaxLayer, &mut Transform)>,
+) {
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    for (layer, mut transform) in layer_query.iter_mut() {
+        // Layers further away follow the camera, so they appear to move less
+        let offset = (camera.focus - layer.origin) * (Vec2::ONE - layer.factor);
+        transform.translation.x = offset.x;
+        transform.translation.y = offset.y;
+    }
+}
@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::level::{LevelEntity, OrcSpawnPoint, PlayerSpawn, TriggerZone};
+use crate::level::parallax::ParallaxLayer;
+use crate::level::tiled::{TiledLayer, TiledLayerData, TiledMap, TiledObject};
+use crate::lighting::{spawn_torch, LevelLighting};
+use crate::pickup::{spawn_pickup, PickupKind};
+use crate::world;
+
+/// z of the first map layer, each following layer is drawn slightly above
+pub const LEVEL_LAYER_Z: f32 = -1.0;
+const LAYER_Z_STEP: f32 = 0.01;
+/// z of layers with the `foreground` property, drawn over the characters
+pub const FOREGROUND_Z: f32 = 100.0;
+
+/// Converts Tiled pixel coordinates (y down, origin top-left) into world
+/// coordinates (y up), with the map centered on the world origin
+pub struct MapTransform {
+    pub scale: f32,
+    pub size: Vec2,
+}
+
+impl MapTransform {
+    pub fn new(map: &TiledMap) -> Self {
+        let scale = map.property("scale").and_then(|v| v.parse().ok()).unwrap_or(1.0);
+        let size = UVec2::new(map.width, map.height) * map.tile_size;
+        Self { scale, size: size.as_vec2() }
+    }
+
+    pub fn to_world(&self, pixel: Vec2) -> Vec2 {
+        Vec2::new(pixel.x - self.size.x / 2.0, self.size.y / 2.0 - pixel.y) * self.scale
+    }
+}
+
+/// Spawn every layer of a Tiled map as level entities, returning the
+/// world space area the map covers
+/// Pickups are left out when `spawn_pickups` is false, a save brings back
+/// the ones that weren't collected
+pub fn spawn_tiled_map(commands: &mut Commands, map: &TiledMap, spawn_pickups: bool) -> Rect {
+    let transform = MapTransform::new(map);
+    let parallax_origin = transform.to_world(map.parallax_origin);
+
+    for (index, layer) in map.layers.iter().enumerate() {
+        let z = LEVEL_LAYER_Z + index as f32 * LAYER_Z_STEP;
+
+        match &layer.data {
+            TiledLayerData::Image(image) => {
+                if layer.visible {
+                    let root = spawn_layer_root(commands, layer, z, parallax_origin);
+                    let top_left = transform.to_world(layer.offset).extend(0.0);
+                    let background = world::spawn_background(commands, image.clone(), top_left, transform.scale);
+                    commands.entity(root).add_child(background);
+                }
+            }
+            TiledLayerData::Tiles(gids) => {
+                if layer.visible {
+                    let root = spawn_layer_root(commands, layer, z, parallax_origin);
+                    spawn_tiles(commands, root, map, &transform, gids, layer.offset);
+                }
+
+                // Collision layers may be hidden in the editor, they still block
+                if layer.name == "collision" || layer.property("collision") == Some("true") {
+                    let solid: Vec<bool> = gids.iter().map(|gid| *gid != 0).collect();
+                    spawn_tile_colliders(commands, map, &transform, &solid);
+                }
+            }
+            TiledLayerData::Objects(objects) => {
+                for object in objects {
+                    spawn_object(commands, &transform, object, spawn_pickups);
+                }
+            }
+        }
+    }
+
+    Rect::from_corners(transform.to_world(Vec2::ZERO), transform.to_world(transform.size))
+}
+
+/// Ambient light from the map properties: `darkness` (0 to 1) and
+/// `ambient_color` (a Tiled color)
+pub fn map_lighting(map: &TiledMap) -> LevelLighting {
+    let mut lighting = LevelLighting::default();
+    if let Some(darkness) = map.property("darkness").and_then(|v| v.parse().ok()) {
+        lighting.darkness = darkness;
+    }
+    // Tiled writes colors as #AARRGGBB, the alpha is not needed here
+    if let Some(color) = map.property("ambient_color") {
+        let hex = color.trim_start_matches('#');
+        let rgb = &hex[hex.len().saturating_sub(6)..];
+        match Srgba::hex(rgb) {
+            Ok(color) => lighting.color = color.into(),
+            Err(_) => warn!("Invalid ambient_color '{}'", color),
+        }
+    }
+    lighting
+}
+
+// Every drawn layer gets a root entity, so it can scroll and be ordered as a whole
+fn spawn_layer_root(commands: &mut Commands, layer: &TiledLayer, z: f32, parallax_origin: Vec2) -> Entity {
+    let z = if layer.property("foreground") == Some("true") {
+        FOREGROUND_Z + (z - LEVEL_LAYER_Z)
+    } else {
+        z
+    };
+
+    let mut root = commands.spawn((
+        Transform::from_xyz(0.0, 0.0, z),
+        Visibility::default(),
+        Name::new(format!("Layer-{}", layer.name)),
+        LevelEntity,
+    ));
+    if layer.parallax != Vec2::ONE {
+        root.insert(ParallaxLayer {
+            factor: layer.parallax,
+            origin: parallax_origin,
+        });
+    }
+    root.id()
+}
+
+fn spawn_tiles(
+    commands: &mut Commands,
+    root: Entity,
+    map: &TiledMap,
+    transform: &MapTransform,
+    gids: &[u32],
+    offset: Vec2,
+) {
+    let tile_size = map.tile_size.as_vec2();
+
+    for (i, gid) in gids.iter().enumerate() {
+        if *gid == 0 {
+            continue;
+        }
+        let Some((tileset, index)) = map.tileset_for(*gid) else {
+            warn!("Tile gid {} has no tileset", gid);
+            continue;
+        };
+
+        let cell = Vec2::new((i as u32 % map.width) as f32, (i as u32 / map.width) as f32);
+        let center = offset + (cell + Vec2::splat(0.5)) * tile_size;
+
+        let tile = commands.spawn((
+            Sprite {
+                image: tileset.image.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: tileset.layout.clone(),
+                    index,
+                }),
+                ..default()
+            },
+            Transform::from_translation(transform.to_world(center).extend(0.0))
+                .with_scale(Vec3::splat(transform.scale)),
+        )).id();
+        commands.entity(root).add_child(tile);
+    }
+}
+
+// One wall per merged rectangle instead of one per tile
+fn spawn_tile_colliders(
+    commands: &mut Commands,
+    map: &TiledMap,
+    transform: &MapTransform,
+    solid: &[bool],
+) {
+    let tile_size = map.tile_size.as_vec2();
+
+    for rect in merge_solid_cells(map.width as usize, map.height as usize, solid) {
+        let min = rect.min.as_vec2() * tile_size;
+        let max = rect.max.as_vec2() * tile_size;
+        let center = transform.to_world((min + max) / 2.0);
+        let half_extents = (max - min) / 2.0 * transform.scale;
+
+        let wall = world::spawn_wall(commands, center, half_extents);
+        commands.entity(wall).insert(LevelEntity);
+    }
+}
+
+fn spawn_object(commands: &mut Commands, transform: &MapTransform, object: &TiledObject, spawn_pickups: bool) {
+    // Point objects have no size, rectangles are anchored at their top-left corner
+    let center = transform.to_world(object.position + object.size / 2.0);
+    let half_extents = object.size / 2.0 * transform.scale;
+    let translation = Transform::from_translation(center.extend(0.0));
+
+    match object.class.as_str() {
+        "player_spawn" => {
+            commands.spawn((PlayerSpawn { name: object.name.clone() }, translation, LevelEntity));
+        }
+        "orc_spawn" => {
+            commands.spawn((OrcSpawnPoint, translation, LevelEntity));
+        }
+        "pickup" if !spawn_pickups => {}
+        "pickup" => {
+            let Some(kind) = object.property("kind").and_then(PickupKind::from_name) else {
+                warn!("Pickup '{}' has no valid kind", object.name);
+                return;
+            };
+            let pickup = spawn_pickup(commands, kind, center);
+            commands.entity(pickup).insert(LevelEntity);
+        }
+        "torch" => {
+            let torch = spawn_torch(commands, center);
+            commands.entity(torch).insert(LevelEntity);
+        }
+        "wall" => {
+            let wall = world::spawn_wall(commands, center, half_extents);
+            commands.entity(wall).insert(LevelEntity);
+        }
+        "trigger" => {
+            commands.spawn((
+                TriggerZone {
+                    name: object.name.clone(),
+                    properties: object.properties.clone(),
+                },
+                Collider::cuboid(half_extents.x, half_extents.y),
+                Sensor,
+                ActiveEvents::COLLISION_EVENTS,
+                translation,
+                Name::new(format!("Trigger-{}", object.name)),
+                LevelEntity,
+            ));
+        }
+        other => {
+            warn!("Unknown object class '{}' on '{}'", other, object.name);
+        }
+    }
+}
+
+/// Merge the solid cells of a row-major grid into as few rectangles as
+/// possible. Rectangles are returned in cell coordinates.
+pub fn merge_solid_cells(width: usize, height: usize, solid: &[bool]) -> Vec<URect> {
+    let mut used = vec![false; width * height];
+    let mut rects = Vec::new();
+    let free = |used: &[bool], x: usize, y: usize| solid[y * width + x] && !used[y * width + x];
+
+    for y in 0..height {
+        for x in 0..width {
+            if !free(&used, x, y) {
+                continue;
+            }
+
+            // Grow right as far as possible...
+            let mut w = 1;
+            while x + w < width && free(&used, x + w, y) {
+                w += 1;
+            }
+
+            // ...then down while the whole run below is solid too
+            let mut h = 1;
+            while y + h < height && (x..x + w).all(|cx| free(&used, cx, y + h)) {
+                h += 1;
+            }
+
+            for cy in y..y + h {
+                for cx in x..x + w {
+                    used[cy * width + cx] = true;
+                }
+            }
+            rects.push(URect::new(x as u32, y as u32, (x + w) as u32, (y + h) as u32));
+        }
+    }
+    rects
+}
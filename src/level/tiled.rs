@@ -0,0 +1,354 @@
+use std::fmt;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext, ReadAssetBytesError};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+// Tiled stores flip flags in the top bits of every tile gid
+const GID_FLAGS_MASK: u32 = 0xF000_0000;
+
+/// A map authored in Tiled (.tmx), with its tilesets and images already requested
+#[derive(Asset, TypePath)]
+pub struct TiledMap {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: UVec2,
+    /// Map pixel the view has to be centered on for parallax layers to sit where they were drawn
+    pub parallax_origin: Vec2,
+    pub properties: HashMap<String, String>,
+    pub tilesets: Vec<TiledTileset>,
+    pub layers: Vec<TiledLayer>,
+    // Every image the map needs, so the map only counts as loaded once they are
+    #[dependency]
+    pub images: Vec<Handle<Image>>,
+}
+
+pub struct TiledTileset {
+    pub first_gid: u32,
+    pub tile_count: u32,
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub tile_size: UVec2,
+}
+
+pub struct TiledLayer {
+    pub name: String,
+    pub visible: bool,
+    pub offset: Vec2,
+    pub parallax: Vec2,
+    pub properties: HashMap<String, String>,
+    pub data: TiledLayerData,
+}
+
+pub enum TiledLayerData {
+    /// Row-major tile gids, 0 meaning an empty cell
+    Tiles(Vec<u32>),
+    Objects(Vec<TiledObject>),
+    Image(Handle<Image>),
+}
+
+pub struct TiledObject {
+    pub name: String,
+    pub class: String,
+    /// Top-left corner in map pixels (the position itself for point objects)
+    pub position: Vec2,
+    pub size: Vec2,
+    pub properties: HashMap<String, String>,
+}
+
+impl TiledMap {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(String::as_str)
+    }
+
+    /// Find the tileset a gid belongs to, and the index of the tile inside it
+    pub fn tileset_for(&self, gid: u32) -> Option<(&TiledTileset, usize)> {
+        self.tilesets
+            .iter()
+            .filter(|tileset| gid >= tileset.first_gid && gid < tileset.first_gid + tileset.tile_count)
+            .map(|tileset| (tileset, (gid - tileset.first_gid) as usize))
+            .next()
+    }
+}
+
+impl TiledLayer {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(String::as_str)
+    }
+}
+
+impl TiledObject {
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Debug)]
+pub enum TiledError {
+    Io(std::io::Error),
+    ReadTileset(ReadAssetBytesError),
+    Xml(roxmltree::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledError::Io(err) => write!(f, "could not read map: {err}"),
+            TiledError::ReadTileset(err) => write!(f, "could not read external tileset: {err}"),
+            TiledError::Xml(err) => write!(f, "invalid XML: {err}"),
+            TiledError::Invalid(message) => write!(f, "invalid Tiled map: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {}
+
+impl From<std::io::Error> for TiledError {
+    fn from(err: std::io::Error) -> Self {
+        TiledError::Io(err)
+    }
+}
+
+impl From<roxmltree::Error> for TiledError {
+    fn from(err: roxmltree::Error) -> Self {
+        TiledError::Xml(err)
+    }
+}
+
+#[derive(Default)]
+pub struct TiledMapLoader;
+
+impl AssetLoader for TiledMapLoader {
+    type Asset = TiledMap;
+    type Settings = ();
+    type Error = TiledError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes)
+            .map_err(|_| TiledError::Invalid("map is not valid UTF-8".into()))?;
+        let document = roxmltree::Document::parse(&text)?;
+        let map = document.root_element();
+        if map.tag_name().name() != "map" {
+            return Err(TiledError::Invalid("root element is not <map>".into()));
+        }
+        if map.attribute("orientation").unwrap_or("orthogonal") != "orthogonal" {
+            return Err(TiledError::Invalid("only orthogonal maps are supported".into()));
+        }
+
+        let width = parse_attribute(map, "width")?;
+        let height = parse_attribute(map, "height")?;
+        let tile_size = UVec2::new(parse_attribute(map, "tilewidth")?, parse_attribute(map, "tileheight")?);
+
+        let mut images = Vec::new();
+        let mut tilesets = Vec::new();
+        let mut layers = Vec::new();
+
+        for node in map.children().filter(roxmltree::Node::is_element) {
+            match node.tag_name().name() {
+                "tileset" => {
+                    let first_gid = parse_attribute(node, "firstgid")?;
+                    let index = tilesets.len();
+
+                    // External tilesets live in their own .tsx file next to the map
+                    let tileset = match node.attribute("source") {
+                        Some(source) => {
+                            let path = resolve(load_context, source)?;
+                            let tsx_bytes = load_context
+                                .read_asset_bytes(path.clone())
+                                .await
+                                .map_err(TiledError::ReadTileset)?;
+                            let tsx_text = String::from_utf8(tsx_bytes)
+                                .map_err(|_| TiledError::Invalid(format!("{source} is not valid UTF-8")))?;
+                            let tsx = roxmltree::Document::parse(&tsx_text)?;
+                            load_tileset(load_context, tsx.root_element(), &path, first_gid, index)?
+                        }
+                        None => {
+                            let map_path = load_context.asset_path().clone();
+                            load_tileset(load_context, node, &map_path, first_gid, index)?
+                        }
+                    };
+                    images.push(tileset.image.clone());
+                    tilesets.push(tileset);
+                }
+                "layer" => {
+                    let data = node
+                        .children()
+                        .find(|child| child.has_tag_name("data"))
+                        .ok_or_else(|| TiledError::Invalid("tile layer without <data>".into()))?;
+                    if data.attribute("encoding") != Some("csv") {
+                        return Err(TiledError::Invalid(
+                            "only CSV tile layer encoding is supported".into(),
+                        ));
+                    }
+                    let gids = data
+                        .text()
+                        .unwrap_or_default()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|value| !value.is_empty())
+                        .map(|value| {
+                            value
+                                .parse::<u32>()
+                                .map(|gid| gid & !GID_FLAGS_MASK)
+                                .map_err(|_| TiledError::Invalid(format!("bad tile gid '{value}'")))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if gids.len() != (width * height) as usize {
+                        return Err(TiledError::Invalid(format!(
+                            "layer has {} tiles, expected {}",
+                            gids.len(),
+                            width * height
+                        )));
+                    }
+                    layers.push(parse_layer(node, TiledLayerData::Tiles(gids)));
+                }
+                "objectgroup" => {
+                    let objects = node
+                        .children()
+                        .filter(|child| child.has_tag_name("object"))
+                        .map(parse_object)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    layers.push(parse_layer(node, TiledLayerData::Objects(objects)));
+                }
+                "imagelayer" => {
+                    let Some(source) = node
+                        .children()
+                        .find(|child| child.has_tag_name("image"))
+                        .and_then(|image| image.attribute("source"))
+                    else {
+                        continue; // Tiled allows empty image layers
+                    };
+                    let image: Handle<Image> = load_context.load(resolve(load_context, source)?);
+                    images.push(image.clone());
+                    layers.push(parse_layer(node, TiledLayerData::Image(image)));
+                }
+                _ => {}
+            }
+        }
+
+        let origin = |name: &str| map.attribute(name).and_then(|v| v.parse().ok()).unwrap_or(0.0);
+        let parallax_origin = Vec2::new(origin("parallaxoriginx"), origin("parallaxoriginy"));
+
+        Ok(TiledMap {
+            width,
+            height,
+            tile_size,
+            parallax_origin,
+            properties: parse_properties(map),
+            tilesets,
+            layers,
+            images,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmx"]
+    }
+}
+
+fn load_tileset(
+    load_context: &mut LoadContext<'_>,
+    node: roxmltree::Node,
+    path: &bevy::asset::AssetPath<'static>,
+    first_gid: u32,
+    index: usize,
+) -> Result<TiledTileset, TiledError> {
+    let tile_size = UVec2::new(parse_attribute(node, "tilewidth")?, parse_attribute(node, "tileheight")?);
+    let tile_count: u32 = parse_attribute(node, "tilecount")?;
+    let columns: u32 = parse_attribute(node, "columns")?;
+    let spacing = node.attribute("spacing").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let margin = node.attribute("margin").and_then(|v| v.parse().ok()).unwrap_or(0);
+
+    let source = node
+        .children()
+        .find(|child| child.has_tag_name("image"))
+        .and_then(|image| image.attribute("source"))
+        .ok_or_else(|| TiledError::Invalid("only single-image tilesets are supported".into()))?;
+    let image_path = path
+        .resolve_embed(source)
+        .map_err(|err| TiledError::Invalid(format!("bad image path '{source}': {err}")))?;
+
+    let rows = tile_count.div_ceil(columns.max(1));
+    let layout = TextureAtlasLayout::from_grid(
+        tile_size,
+        columns,
+        rows,
+        Some(UVec2::splat(spacing)),
+        Some(UVec2::splat(margin)),
+    );
+
+    Ok(TiledTileset {
+        first_gid,
+        tile_count,
+        image: load_context.load(image_path),
+        layout: load_context.add_labeled_asset(format!("tileset{index}"), layout),
+        tile_size,
+    })
+}
+
+// Paths in a map are relative to the map file itself
+fn resolve(load_context: &LoadContext<'_>, source: &str) -> Result<bevy::asset::AssetPath<'static>, TiledError> {
+    load_context
+        .asset_path()
+        .resolve_embed(source)
+        .map_err(|err| TiledError::Invalid(format!("bad path '{source}': {err}")))
+}
+
+fn parse_layer(node: roxmltree::Node, data: TiledLayerData) -> TiledLayer {
+    let float = |name: &str, default: f32| {
+        node.attribute(name).and_then(|v| v.parse().ok()).unwrap_or(default)
+    };
+    TiledLayer {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        visible: node.attribute("visible") != Some("0"),
+        offset: Vec2::new(float("offsetx", 0.0), float("offsety", 0.0)),
+        parallax: Vec2::new(float("parallaxx", 1.0), float("parallaxy", 1.0)),
+        properties: parse_properties(node),
+        data,
+    }
+}
+
+fn parse_object(node: roxmltree::Node) -> Result<TiledObject, TiledError> {
+    let float = |name: &str| node.attribute(name).and_then(|v| v.parse().ok()).unwrap_or(0.0);
+    Ok(TiledObject {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        // Tiled 1.9 renamed "type" to "class"
+        class: node
+            .attribute("class")
+            .or_else(|| node.attribute("type"))
+            .unwrap_or_default()
+            .to_string(),
+        position: Vec2::new(float("x"), float("y")),
+        size: Vec2::new(float("width"), float("height")),
+        properties: parse_properties(node),
+    })
+}
+
+fn parse_properties(node: roxmltree::Node) -> HashMap<String, String> {
+    node.children()
+        .filter(|child| child.has_tag_name("properties"))
+        .flat_map(|properties| properties.children().filter(|child| child.has_tag_name("property")))
+        .filter_map(|property| {
+            let name = property.attribute("name")?;
+            // Multi-line string properties store their value as text
+            let value = property.attribute("value").or_else(|| property.text())?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn parse_attribute<T: std::str::FromStr>(node: roxmltree::Node, name: &str) -> Result<T, TiledError> {
+    node.attribute(name)
+        .ok_or_else(|| TiledError::Invalid(format!("<{}> is missing '{name}'", node.tag_name().name())))?
+        .parse()
+        .map_err(|_| TiledError::Invalid(format!("<{}> has an invalid '{name}'", node.tag_name().name())))
+}
@@ -0,0 +1,208 @@
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::level::{CurrentLevel, LevelEntity, LevelSource, TriggerZone};
+use crate::rng::GameRng;
+use crate::player_code::Player;
+
+const FADE_DURATION: f32 = 0.4;
+
+/// Ask for the current room to be swapped for another level
+#[derive(Event)]
+pub struct LevelTransitionEvent {
+    /// Asset path of the level to load, or [`DUNGEON_LEVEL`](crate::level::DUNGEON_LEVEL)
+    pub level: String,
+    /// Name of the player spawn to use in the new level
+    pub spawn_point: Option<String>,
+}
+
+#[derive(Default, PartialEq)]
+enum TransitionPhase {
+    #[default]
+    Idle,
+    FadingOut,
+    Loading,
+    FadingIn,
+}
+
+#[derive(Resource, Default)]
+struct LevelTransition {
+    phase: TransitionPhase,
+    timer: Timer,
+    target: Option<LevelTransitionEvent>,
+    /// Level being left and its spawn point, put back if the next one fails to load
+    previous: Option<(LevelSource, Option<String>)>,
+}
+
+impl LevelTransition {
+    fn start(&mut self, phase: TransitionPhase) {
+        self.phase = phase;
+        self.timer = Timer::from_seconds(FADE_DURATION, TimerMode::Once);
+    }
+}
+
+// Full screen black overlay used for the fade
+#[derive(Component)]
+struct FadeOverlay;
+
+pub struct LevelTransitionPlugin;
+
+impl Plugin for LevelTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<LevelTransitionEvent>()
+            .init_resource::<LevelTransition>()
+            .add_systems(Startup, setup_fade_overlay)
+            .add_systems(Update, (
+                detect_door_triggers,
+                start_level_transition.after(detect_door_triggers),
+                update_level_transition.after(start_level_transition),
+            ));
+    }
+}
+
+fn setup_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+        GlobalZIndex(100), // Above every other UI element
+        FadeOverlay,
+    ));
+}
+
+// Doors are trigger zones with a `door_to` property
+fn detect_door_triggers(
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<Entity, With<Player>>,
+    trigger_query: Query<&TriggerZone>,
+    mut transition_events: EventWriter<LevelTransitionEvent>,
+) {
+    let Ok(player) = player_query.get_single() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = *event else {
+            continue;
+        };
+        let other = if e1 == player {
+            e2
+        } else if e2 == player {
+            e1
+        } else {
+            continue;
+        };
+
+        if let Ok(trigger) = trigger_query.get(other) {
+            if let Some(level) = trigger.properties.get("door_to") {
+                info!("Player entered door '{}' to {}", trigger.name, level);
+                transition_events.send(LevelTransitionEvent {
+                    level: level.clone(),
+                    spawn_point: trigger.properties.get("door_spawn").cloned(),
+                });
+            }
+        }
+    }
+}
+
+fn start_level_transition(
+    mut transition_events: EventReader<LevelTransitionEvent>,
+    mut transition: ResMut<LevelTransition>,
+) {
+    for event in transition_events.read() {
+        // Ignore doors touched while already switching rooms
+        if transition.phase != TransitionPhase::Idle {
+            continue;
+        }
+        transition.target = Some(LevelTransitionEvent {
+            level: event.level.clone(),
+            spawn_point: event.spawn_point.clone(),
+        });
+        transition.start(TransitionPhase::FadingOut);
+    }
+}
+
+fn update_level_transition(
+    mut commands: Commands,
+    mut transition: ResMut<LevelTransition>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut overlay_query: Query<&mut BackgroundColor, With<FadeOverlay>>,
+    mut player_query: Query<&mut Velocity, With<Player>>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    asset_server: Res<AssetServer>,
+    rng: Res<GameRng>,
+    time: Res<Time>,
+) {
+    if transition.phase == TransitionPhase::Idle {
+        return;
+    }
+    transition.timer.tick(time.delta());
+
+    // Keep the player still while the screen is dark
+    if let Ok(mut velocity) = player_query.get_single_mut() {
+        velocity.linvel = Vec2::ZERO;
+    }
+
+    let alpha = match transition.phase {
+        TransitionPhase::FadingOut => transition.timer.fraction(),
+        TransitionPhase::FadingIn => 1.0 - transition.timer.fraction(),
+        _ => 1.0,
+    };
+    if let Ok(mut overlay) = overlay_query.get_single_mut() {
+        overlay.0.set_alpha(alpha);
+    }
+
+    match transition.phase {
+        TransitionPhase::FadingOut if transition.timer.finished() => {
+            // Tear down the old room and ask for the next one
+            for entity in level_entities.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            if let Some(target) = transition.target.take() {
+                let source = LevelSource::from_target(&target.level, &current_level.source, &rng, &asset_server);
+                let previous = std::mem::replace(&mut *current_level, CurrentLevel {
+                    source,
+                    spawn_point: target.spawn_point,
+                    spawned: false,
+                    from_save: false,
+                });
+                transition.previous = Some((previous.source, previous.spawn_point));
+            }
+            transition.phase = TransitionPhase::Loading;
+        }
+        TransitionPhase::Loading if current_level.spawned => {
+            transition.previous = None;
+            transition.start(TransitionPhase::FadingIn);
+        }
+        TransitionPhase::Loading => {
+            // A map that fails to load would leave the screen black for good,
+            // e.g. after a typo in a door's target
+            let LevelSource::Map(handle) = &current_level.source else {
+                return;
+            };
+            if !matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) {
+                return;
+            }
+            warn!("Could not load level {:?}, going back", handle.path());
+            let (source, spawn_point) = transition
+                .previous
+                .take()
+                .unwrap_or_else(|| (LevelSource::start(&asset_server, &rng), None));
+            *current_level = CurrentLevel {
+                source,
+                spawn_point,
+                spawned: false,
+                from_save: false,
+            };
+        }
+        TransitionPhase::FadingIn if transition.timer.finished() => {
+            transition.phase = TransitionPhase::Idle;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,122 @@
+//! Misspelled: typing spells against orcs.
+//!
+//! The game binary adds a window, rendering, audio and menus around
+//! [`GameLogicPlugins`]. [`headless_app`] runs the same rules without any
+//! of that, so scenarios can be scripted and stepped frame by frame in tests.
+
+use std::time::Duration;
+
+use bevy::app::PluginGroupBuilder;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::text::Font;
+use bevy::time::TimeUpdateStrategy;
+use bevy_rapier2d::prelude::*;
+
+pub mod audio;
+pub mod loading;
+pub mod animation;
+pub mod camera;
+pub mod world;
+pub mod ysort;
+pub mod level;
+pub mod lighting;
+pub mod particles;
+pub mod pickup;
+pub mod navigation;
+pub mod rng;
+pub mod replay;
+pub mod storage;
+pub mod save;
+pub mod leaderboard;
+pub mod ai;
+pub mod ui_hp_display;
+pub mod ui_orc_counter;
+pub mod ui_boss_health;
+pub mod ui_main_menu;
+pub mod ui_game_over;
+pub mod player_movement;
+pub mod player_code;
+pub mod player_animation;
+pub mod orc;
+pub mod spell;
+pub mod spellbook;
+pub mod spell_assets;
+pub mod blink;
+pub mod fireball;
+pub mod shield;
+
+use level::{CurrentLevel, LevelSource};
+use loading::AppState;
+use rng::GameRng;
+
+/// Length of a frame of the headless app, every `App::update` advances time by this much
+pub const HEADLESS_TIMESTEP: f32 = 1.0 / 60.0;
+/// Seed of the headless app, so a scenario plays out the same way on every run
+pub const HEADLESS_SEED: u64 = 0;
+
+/// The rules of the game: physics, the player, orcs and spells.
+/// None of these need a window, a renderer or an audio device.
+pub struct GameLogicPlugins;
+
+impl PluginGroup for GameLogicPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(loading::LoadingPlugin)
+            .add(RapierPhysicsPlugin::<NoUserData>::default())
+            .add(player_code::PlayerHealthPlugin)
+            .add(player_code::PlayerPlugin)
+            .add(animation::AnimationPlugin)
+            .add(rng::RngPlugin)
+            .add(navigation::NavigationPlugin)
+            .add(ai::AiPlugin)
+            .add(orc::OrcPlugin)
+            .add(spell_assets::SpellAssetPlugin)
+            .add(spell::StackSpellSystemPlugin)
+            .add(fireball::FireballPlugin)
+            .add(blink::BlinkPlugin)
+            .add(shield::ShieldPlugin)
+            .add(spellbook::SpellbookPlugin)
+            .add(ui_orc_counter::OrcDeathCounterPlugin)
+    }
+}
+
+/// The game logic on `MinimalPlugins`, already in game. `LevelPlugin` spawns
+/// an empty [`LevelSource::Arena`] on the first update. Time advances by
+/// [`HEADLESS_TIMESTEP`] per update, scenarios place the orcs themselves,
+/// e.g. with `OrcSpawnPoint`s.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        TransformPlugin,
+        HierarchyPlugin,
+        InputPlugin,
+        AssetPlugin::default(),
+    ))
+    // Asset types the renderer would otherwise register, sprites and text
+    // keep their handles even though nothing draws them
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlasLayout>()
+    .init_asset::<Font>()
+    .init_asset::<Mesh>()
+    // Sent by gameplay code, read by the camera and the audio plugins
+    .add_event::<camera::ScreenShakeEvent>()
+    .add_event::<audio::PlaySfx>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(HEADLESS_TIMESTEP)))
+    // Nothing to wait for, so skip the loading screen and the main menu
+    .insert_state(AppState::InGame)
+    .add_plugins(GameLogicPlugins)
+    .insert_resource(GameRng::from_seed(HEADLESS_SEED))
+    // Picked before LevelPlugin starts, so it doesn't load the library
+    .insert_resource(CurrentLevel {
+        source: LevelSource::Arena,
+        spawn_point: None,
+        spawned: false,
+        from_save: false,
+    })
+    .add_plugins(level::LevelPlugin);
+    app
+}
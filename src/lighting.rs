@@ -0,0 +1,237 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef, ShaderType};
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+use bevy::transform::TransformSystem;
+
+use crate::camera::SmoothCamera;
+
+/// Must match MAX_LIGHTS in assets/shaders/lighting.wgsl
+const MAX_LIGHTS: usize = 32;
+const LIGHTING_SHADER: &str = "shaders/lighting.wgsl";
+
+/// Local z of the darkness overlay under the camera, above every world layer
+const OVERLAY_Z: f32 = 900.0;
+/// The overlay is a bit larger than the view so screen shake rotation can't uncover the edges
+const OVERLAY_MARGIN: f32 = 1.2;
+
+const TORCH_FLICKER_SPEED: f32 = 8.0;
+const TORCH_FLICKER_AMOUNT: f32 = 0.15;
+
+/// How dark the current room is, set when a level spawns
+#[derive(Resource, Clone)]
+pub struct LevelLighting {
+    /// 0 is fully lit, 1 is pitch black away from lights
+    pub darkness: f32,
+    pub color: Color,
+}
+
+impl Default for LevelLighting {
+    fn default() -> Self {
+        Self {
+            darkness: 0.0,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// Point light following its entity
+#[derive(Component, Clone)]
+pub struct Light2d {
+    pub color: Color,
+    /// World units until the light fades out completely
+    pub radius: f32,
+    pub intensity: f32,
+}
+
+impl Light2d {
+    pub fn torch() -> Self {
+        Self {
+            color: Color::srgb(1.0, 0.65, 0.3),
+            radius: 260.0,
+            intensity: 1.2,
+        }
+    }
+}
+
+/// Makes a light waver like a flame
+#[derive(Component)]
+pub struct Flicker;
+
+/// Short-lived light that fades out and despawns itself
+#[derive(Component)]
+pub struct LightFlash {
+    timer: Timer,
+    peak: f32,
+}
+
+#[derive(Component)]
+struct LightingOverlay;
+
+#[derive(Asset, TypePath, AsBindGroup, Clone)]
+struct LightingMaterial {
+    #[uniform(0)]
+    lighting: LightingUniform,
+}
+
+#[derive(ShaderType, Clone, Default)]
+struct LightingUniform {
+    ambient: Vec4,
+    light_count: u32,
+    lights: [PointLightUniform; MAX_LIGHTS],
+}
+
+#[derive(ShaderType, Clone, Copy, Default)]
+struct PointLightUniform {
+    position_radius: Vec4,
+    color: Vec4,
+}
+
+impl Material2d for LightingMaterial {
+    fn fragment_shader() -> ShaderRef {
+        LIGHTING_SHADER.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+pub struct LightingPlugin;
+
+impl Plugin for LightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<LightingMaterial>::default())
+            .init_resource::<LevelLighting>()
+            .add_systems(Update, (attach_lighting_overlay, fade_light_flashes))
+            .add_systems(
+                PostUpdate,
+                update_lighting_overlay.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Spawn a light that fades out over `duration` seconds
+pub fn spawn_light_flash(commands: &mut Commands, position: Vec2, color: Color, radius: f32, duration: f32) {
+    let peak = 2.0;
+    commands.spawn((
+        Light2d { color, radius, intensity: peak },
+        LightFlash {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            peak,
+        },
+        Transform::from_translation(position.extend(0.0)),
+        Name::new("LightFlash"),
+    ));
+}
+
+/// Wall torch: a small flame with a flickering light
+pub fn spawn_torch(commands: &mut Commands, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color: Color::srgb(1.0, 0.7, 0.25),
+                custom_size: Some(Vec2::new(6.0, 10.0)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(-0.1)),
+            Light2d::torch(),
+            Flicker,
+            Name::new("Torch"),
+        ))
+        .id()
+}
+
+// The overlay rides along with the camera so it always covers the view
+fn attach_lighting_overlay(
+    mut commands: Commands,
+    camera_query: Query<Entity, Added<SmoothCamera>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<LightingMaterial>>,
+) {
+    for camera in camera_query.iter() {
+        let overlay = commands.spawn((
+            Mesh2d(meshes.add(Rectangle::new(1.0, 1.0))),
+            MeshMaterial2d(materials.add(LightingMaterial {
+                lighting: LightingUniform::default(),
+            })),
+            Transform::from_xyz(0.0, 0.0, OVERLAY_Z),
+            LightingOverlay,
+            Name::new("LightingOverlay"),
+        )).id();
+        commands.entity(camera).add_child(overlay);
+    }
+}
+
+fn fade_light_flashes(
+    mut commands: Commands,
+    mut flash_query: Query<(Entity, &mut LightFlash, &mut Light2d)>,
+    time: Res<Time>,
+) {
+    for (entity, mut flash, mut light) in flash_query.iter_mut() {
+        flash.timer.tick(time.delta());
+        light.intensity = flash.peak * (1.0 - flash.timer.fraction());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn update_lighting_overlay(
+    level_lighting: Res<LevelLighting>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<SmoothCamera>>,
+    mut overlay_query: Query<(&MeshMaterial2d<LightingMaterial>, &mut Transform, &mut Visibility), With<LightingOverlay>>,
+    light_query: Query<(Entity, &Light2d, &GlobalTransform, Option<&Flicker>)>,
+    mut materials: ResMut<Assets<LightingMaterial>>,
+    time: Res<Time>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+    let Ok((material_handle, mut overlay_transform, mut visibility)) = overlay_query.get_single_mut() else {
+        return;
+    };
+
+    // Fully lit rooms don't need the pass at all
+    if level_lighting.darkness <= 0.0 {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Inherited;
+
+    let view_size = projection.area.size();
+    overlay_transform.scale = (view_size * OVERLAY_MARGIN).extend(1.0);
+
+    let Some(material) = materials.get_mut(&material_handle.0) else {
+        return;
+    };
+
+    // With more lights than slots, the ones closest to the camera win
+    let center = camera_transform.translation().truncate();
+    let mut lights: Vec<_> = light_query
+        .iter()
+        .map(|(entity, light, transform, flicker)| {
+            let mut intensity = light.intensity;
+            if flicker.is_some() {
+                let t = time.elapsed_secs() * TORCH_FLICKER_SPEED + entity.index() as f32;
+                intensity *= 1.0 - TORCH_FLICKER_AMOUNT * (0.5 + 0.5 * (t.sin() * (t * 2.3).sin()));
+            }
+            (transform.translation().truncate(), light, intensity)
+        })
+        .filter(|(position, light, _)| {
+            // Skip lights whose glow can't reach the view
+            let reach = view_size / 2.0 * OVERLAY_MARGIN + Vec2::splat(light.radius);
+            ((*position - center).abs().cmple(reach)).all()
+        })
+        .collect();
+    lights.sort_by(|a, b| a.0.distance_squared(center).total_cmp(&b.0.distance_squared(center)));
+
+    let uniform = &mut material.lighting;
+    let ambient = level_lighting.color.to_linear();
+    uniform.ambient = Vec4::new(ambient.red, ambient.green, ambient.blue, level_lighting.darkness.clamp(0.0, 1.0));
+    uniform.light_count = lights.len().min(MAX_LIGHTS) as u32;
+    for (slot, (position, light, intensity)) in uniform.lights.iter_mut().zip(lights) {
+        let color = light.color.to_linear();
+        slot.position_radius = Vec4::new(position.x, position.y, light.radius, 0.0);
+        slot.color = Vec4::new(color.red, color.green, color.blue, intensity);
+    }
+}
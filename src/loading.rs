@@ -0,0 +1,272 @@
+use bevy::asset::{RecursiveDependencyLoadState, UntypedAssetId};
+use bevy::image::Image;
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Seconds the loading screen stays up to list missing assets before the game starts anyway
+const MISSING_NOTICE: f32 = 4.0;
+/// Size of the squares of the placeholder checkerboard, in pixels
+const PLACEHOLDER_CHECKER: u32 = 8;
+
+#[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum AppState {
+    /// Waiting on every `RequiredAssets` handle
+    #[default]
+    Loading,
+    /// Picking a save slot
+    MainMenu,
+    /// Playing, the only state in which gameplay time runs
+    InGame,
+    /// The player died, the run is on the leaderboard
+    GameOver,
+}
+
+/// What stands in for an asset that failed to load
+#[derive(Clone, Copy)]
+enum Fallback {
+    /// Checkerboard of the expected size, so atlas frames still line up
+    Image(UVec2),
+    /// Bevy's built-in font
+    Font,
+    /// Nothing, the owner checks `RequiredAssets::is_missing` and does without
+    Skip,
+}
+
+enum Status {
+    Loading,
+    Loaded,
+    Missing(String),
+}
+
+struct RequiredAsset {
+    path: String,
+    handle: UntypedHandle,
+    fallback: Fallback,
+    /// Failing only gets logged, it doesn't hold up the loading screen
+    optional: bool,
+    status: Status,
+}
+
+/// Assets the game can't run properly without. Loading through here keeps
+/// the game in `AppState::Loading` until they are all done, and swaps in a
+/// fallback for every asset that fails.
+#[derive(Resource, Default)]
+pub struct RequiredAssets {
+    assets: Vec<RequiredAsset>,
+}
+
+impl RequiredAssets {
+    /// Load an image, replaced by a checkerboard of `size` pixels if it fails
+    pub fn image(&mut self, asset_server: &AssetServer, path: &str, size: UVec2) -> Handle<Image> {
+        self.track(asset_server, path, Fallback::Image(size))
+    }
+
+    /// Load a font, replaced by the default font if it fails
+    pub fn font(&mut self, asset_server: &AssetServer, path: &str) -> Handle<Font> {
+        self.track(asset_server, path, Fallback::Font)
+    }
+
+    /// Load any other asset. Nothing replaces it, check `is_missing` before using it.
+    pub fn load<A: Asset>(&mut self, asset_server: &AssetServer, path: &str) -> Handle<A> {
+        self.track(asset_server, path, Fallback::Skip)
+    }
+
+    /// Load an asset the game plays fine without, like a sound. It is waited
+    /// for like the others, but isn't listed on the loading screen if it fails.
+    /// Check `is_missing` before using it.
+    pub fn optional<A: Asset>(&mut self, asset_server: &AssetServer, path: &str) -> Handle<A> {
+        let handle = self.track(asset_server, path, Fallback::Skip);
+        let id = handle.id().untyped();
+        if let Some(asset) = self.assets.iter_mut().find(|asset| asset.handle.id() == id) {
+            asset.optional = true;
+        }
+        handle
+    }
+
+    pub fn is_missing<A: Asset>(&self, handle: &Handle<A>) -> bool {
+        let id = handle.id().untyped();
+        self.assets
+            .iter()
+            .any(|asset| asset.handle.id() == id && matches!(asset.status, Status::Missing(_)))
+    }
+
+    fn track<A: Asset>(&mut self, asset_server: &AssetServer, path: &str, fallback: Fallback) -> Handle<A> {
+        let handle: Handle<A> = asset_server.load(path.to_string());
+        if !self.assets.iter().any(|asset| asset.handle.id() == handle.id().untyped()) {
+            self.assets.push(RequiredAsset {
+                path: path.to_string(),
+                handle: handle.clone().untyped(),
+                fallback,
+                optional: false,
+                status: Status::Loading,
+            });
+        }
+        handle
+    }
+
+    fn pending(&self) -> usize {
+        self.assets.iter().filter(|asset| matches!(asset.status, Status::Loading)).count()
+    }
+
+    fn missing(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.assets.iter().filter_map(|asset| match &asset.status {
+            Status::Missing(reason) if !asset.optional => Some((asset.path.as_str(), reason.as_str())),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct LoadingText;
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .init_resource::<RequiredAssets>()
+            .add_systems(OnEnter(AppState::Loading), (spawn_loading_screen, pause_time))
+            .add_systems(OnExit(AppState::Loading), despawn_loading_screen)
+            .add_systems(OnEnter(AppState::InGame), resume_time)
+            .add_systems(OnExit(AppState::InGame), pause_time)
+            .add_systems(
+                Update,
+                (check_required_assets, update_loading_screen)
+                    .chain()
+                    .run_if(in_state(AppState::Loading)),
+            );
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn check_required_assets(
+    mut required: ResMut<RequiredAssets>,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    mut fonts: ResMut<Assets<Font>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    real_time: Res<Time<Real>>,
+    mut notice: Local<f32>,
+) {
+    for asset in required.assets.iter_mut() {
+        if !matches!(asset.status, Status::Loading) {
+            continue;
+        }
+        match asset_server.get_recursive_dependency_load_state(asset.handle.id()) {
+            Some(RecursiveDependencyLoadState::Loaded) => asset.status = Status::Loaded,
+            Some(RecursiveDependencyLoadState::Failed(err)) => {
+                if asset.optional {
+                    warn!("Could not load {}, going without it: {}", asset.path, err);
+                } else {
+                    error!("Could not load {}: {}", asset.path, err);
+                }
+                replace_with_fallback(asset.handle.id(), asset.fallback, &mut images, &mut fonts);
+                asset.status = Status::Missing(err.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    if required.pending() > 0 {
+        return;
+    }
+
+    // Leave the list of missing assets on screen for a moment
+    if required.missing().next().is_some() && *notice < MISSING_NOTICE {
+        *notice += real_time.delta_secs();
+        return;
+    }
+    info!("All required assets loaded");
+    next_state.set(AppState::MainMenu);
+}
+
+fn replace_with_fallback(id: UntypedAssetId, fallback: Fallback, images: &mut Assets<Image>, fonts: &mut Assets<Font>) {
+    match fallback {
+        Fallback::Image(size) => {
+            images.insert(id.typed::<Image>(), placeholder_image(size));
+        }
+        Fallback::Font => {
+            if let Some(default_font) = fonts.get(&Handle::<Font>::default()).cloned() {
+                fonts.insert(id.typed::<Font>(), default_font);
+            }
+        }
+        Fallback::Skip => {}
+    }
+}
+
+/// Magenta and black checkerboard, impossible to mistake for real art
+fn placeholder_image(size: UVec2) -> Image {
+    let size = size.max(UVec2::ONE);
+    let mut data = Vec::with_capacity((size.x * size.y * 4) as usize);
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let magenta = (x / PLACEHOLDER_CHECKER + y / PLACEHOLDER_CHECKER) % 2 == 0;
+            data.extend_from_slice(if magenta { &[255, 0, 255, 255] } else { &[0, 0, 0, 255] });
+        }
+    }
+    Image::new(
+        Extent3d { width: size.x, height: size.y, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    )
+}
+
+fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::BLACK),
+            // Above every other interface element
+            GlobalZIndex(100),
+            LoadingScreen,
+        ))
+        .with_child((
+            Text::new("Loading..."),
+            TextFont { font_size: 24.0, ..default() },
+            TextLayout::new_with_justify(JustifyText::Center),
+            LoadingText,
+        ));
+}
+
+fn update_loading_screen(required: Res<RequiredAssets>, mut text_query: Query<&mut Text, With<LoadingText>>) {
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    let total = required.assets.len();
+    let mut content = format!("Loading... {}/{}", total - required.pending(), total);
+    for (path, reason) in required.missing() {
+        content.push_str(&format!("\nMissing {path}: {reason}"));
+    }
+    if required.missing().next().is_some() {
+        content.push_str("\n\nStarting with placeholders");
+    }
+    text.0 = content;
+}
+
+fn despawn_loading_screen(mut commands: Commands, screen_query: Query<Entity, With<LoadingScreen>>) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -1,30 +1,11 @@
 use bevy::prelude::*;
-use bevy::sprite::TextureAtlasLayout;
 use bevy_rapier2d::prelude::*;
 use bevy_rapier2d::render::{RapierDebugRenderPlugin, DebugRenderContext};
 
-mod audio;
-mod animation;
-mod camera;
-mod world;
-mod ui_hp_display;
-mod ui_orc_counter;
-mod player_movement;
-mod player_code;
-mod player_animation;
-mod orc;
-mod spell;
-mod spellbook;
-mod blink;
-mod fireball;
-mod shield;
-
+use misspelled::*;
 use audio::AudioPlugin;
 use ui_hp_display::{HealthDisplayPlugin};
-use ui_orc_counter::OrcDeathCounterPlugin;
-use crate::player_code::{ PlayerHealthPlugin};
-use orc::OrcPlugin;
-use shield::ShieldPlugin;
+use ui_boss_health::BossHealthBarPlugin;
 
 fn main() {
     App::new()
@@ -43,15 +24,14 @@ fn main() {
                 }),
         )
 
-        // ——— Rapier 2D physics ———
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default())   // Core physics
+        // ——— Game rules: loading, physics, player, orcs & spells ———
+        .add_plugins(GameLogicPlugins)
         .insert_resource(DebugRenderContext {
             enabled: true,     // Turn on all colliders/hurtboxes
             ..default()
         })
         .add_event::<CollisionEvent>()
         //.add_plugins(PlayerPhysicsPlugin)
-        .add_plugins(PlayerHealthPlugin)
 
         // ——— Audio system ———
         .add_plugins(AudioPlugin) // Add the audio plugin
@@ -59,57 +39,28 @@ fn main() {
         // ——— Health display system ———
         .add_plugins(HealthDisplayPlugin) // Add the health display plugin
 
-        // ——— Orc enemy bundle ———
-        .add_plugins(OrcPlugin)
-
-        // ——— Spell-casting systems ———
-        .add_event::<spell::SpellCastEvent>()
-        .add_plugins(spell::StackSpellSystemPlugin)
-        .add_plugins(fireball::FireballPlugin)
-        .add_plugins(blink::BlinkPlugin)
-        .add_plugins(ShieldPlugin)
-        .add_plugins(spellbook::SpellbookPlugin)
-
-        .add_plugins(OrcDeathCounterPlugin)
-
-        // ——— Startup & Update loops ———
-        .add_systems(Startup, setup_game)
-        .add_systems(
-            Update,
-            (
-                player_movement::character_movement,
-                player_animation::update_sprite_direction,
-                player_animation::update_animation_state,
-                animation::execute_animations,
-                camera::update_camera,
-            ),
-        )
-        .run();
-}
-
+        // ——— Camera & draw order ———
+        .add_plugins(camera::CameraPlugin)
+        .add_plugins(ysort::YSortPlugin)
+        .add_plugins(lighting::LightingPlugin)
+        .add_plugins(particles::ParticlePlugin)
 
+        // ——— Levels ———
+        .add_plugins(level::LevelPlugin)
+        .add_plugins(pickup::PickupPlugin)
 
-fn setup_game(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-) {
-    // Setup camera
-    camera::setup_camera(commands.reborrow());
+        // ——— Input replays, after the RNG is seeded ———
+        .add_plugins(replay::ReplayPlugin)
 
+        .add_plugins(BossHealthBarPlugin)
 
-    // Load and spawn the library background
-    let library = asset_server.load("library.png");
-    world::setup_world(commands.reborrow(), library);
-    
-    
-    // Create the texture atlas for character sprite
-    // Layout: 16x32 sprites, 9 columns, 10 rows
-    let texture = asset_server.load("characters_atlas.png");
-    let layout = TextureAtlasLayout::from_grid(UVec2::new(16, 32), 9, 10, None, None);
-    let texture_atlas_layout = atlas_layouts.add(layout);
-    // Spawn the player
-    player_code::setup_player(commands, texture, texture_atlas_layout);
+        // ——— Save slots, leaderboard & menus ———
+        .add_plugins(save::SavePlugin)
+        .add_plugins(leaderboard::LeaderboardPlugin)
+        .add_plugins(ui_main_menu::MainMenuPlugin)
+        .add_plugins(ui_game_over::GameOverPlugin)
 
-    println!("Game setup complete");
-}
\ No newline at end of file
+        // ——— Startup ———
+        .add_systems(Startup, camera::setup_camera)
+        .run();
+}
@@ -0,0 +1,310 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::world::Wall;
+
+/// Size of a navigation cell in world units
+pub const NAV_CELL_SIZE: f32 = 32.0;
+/// Walls are grown by this much so agents don't scrape along them
+const AGENT_RADIUS: f32 = 16.0;
+/// Extra walkable border around the outermost walls
+const GRID_MARGIN: f32 = 2.0 * NAV_CELL_SIZE;
+
+const STRAIGHT_COST: f32 = 1.0;
+const DIAGONAL_COST: f32 = std::f32::consts::SQRT_2;
+
+/// Walkability grid baked from the static wall colliders of the current level
+#[derive(Resource, Default)]
+pub struct NavGrid {
+    origin: Vec2,
+    width: usize,
+    height: usize,
+    blocked: Vec<bool>,
+}
+
+impl NavGrid {
+    /// Build a grid covering all given axis-aligned obstacles (center, half extents)
+    pub fn from_obstacles(obstacles: &[(Vec2, Vec2)]) -> Self {
+        if obstacles.is_empty() {
+            return Self::default();
+        }
+
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for (center, half) in obstacles {
+            min = min.min(*center - *half);
+            max = max.max(*center + *half);
+        }
+        min -= Vec2::splat(GRID_MARGIN);
+        max += Vec2::splat(GRID_MARGIN);
+
+        let width = ((max.x - min.x) / NAV_CELL_SIZE).ceil() as usize;
+        let height = ((max.y - min.y) / NAV_CELL_SIZE).ceil() as usize;
+        let mut grid = Self {
+            origin: min,
+            width,
+            height,
+            blocked: vec![false; width * height],
+        };
+
+        // A cell is blocked if its center lies inside an inflated obstacle
+        for (center, half) in obstacles {
+            let inflated = *half + Vec2::splat(AGENT_RADIUS);
+            let lo = grid.clamp_cell(*center - inflated);
+            let hi = grid.clamp_cell(*center + inflated);
+            for y in lo.y..=hi.y {
+                for x in lo.x..=hi.x {
+                    let cell_center = grid.cell_to_world(UVec2::new(x, y));
+                    let d = (cell_center - *center).abs();
+                    if d.x <= inflated.x && d.y <= inflated.y {
+                        let index = grid.index(UVec2::new(x, y));
+                        grid.blocked[index] = true;
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocked.is_empty()
+    }
+
+    pub fn world_to_cell(&self, pos: Vec2) -> Option<UVec2> {
+        let local = (pos - self.origin) / NAV_CELL_SIZE;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let cell = UVec2::new(local.x as u32, local.y as u32);
+        if (cell.x as usize) < self.width && (cell.y as usize) < self.height {
+            Some(cell)
+        } else {
+            None
+        }
+    }
+
+    /// World position of the center of a cell
+    pub fn cell_to_world(&self, cell: UVec2) -> Vec2 {
+        self.origin + (cell.as_vec2() + Vec2::splat(0.5)) * NAV_CELL_SIZE
+    }
+
+    pub fn is_walkable(&self, cell: UVec2) -> bool {
+        (cell.x as usize) < self.width
+            && (cell.y as usize) < self.height
+            && !self.blocked[self.index(cell)]
+    }
+
+    fn index(&self, cell: UVec2) -> usize {
+        cell.y as usize * self.width + cell.x as usize
+    }
+
+    fn clamp_cell(&self, pos: Vec2) -> UVec2 {
+        let local = ((pos - self.origin) / NAV_CELL_SIZE).max(Vec2::ZERO);
+        UVec2::new(
+            (local.x as u32).min(self.width.saturating_sub(1) as u32),
+            (local.y as u32).min(self.height.saturating_sub(1) as u32),
+        )
+    }
+
+    /// Closest walkable cell to `cell`, searching outwards ring by ring
+    fn nearest_walkable(&self, cell: UVec2, max_radius: u32) -> Option<UVec2> {
+        if self.is_walkable(cell) {
+            return Some(cell);
+        }
+        for radius in 1..=max_radius as i32 {
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    if dx.abs() != radius && dy.abs() != radius {
+                        continue; // Only the ring, the inside was already checked
+                    }
+                    let candidate = cell.as_ivec2() + IVec2::new(dx, dy);
+                    if candidate.x < 0 || candidate.y < 0 {
+                        continue;
+                    }
+                    let candidate = candidate.as_uvec2();
+                    if self.is_walkable(candidate) {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Walk the cells between two points and check none of them are blocked
+    pub fn has_line_of_sight(&self, from: Vec2, to: Vec2) -> bool {
+        let distance = from.distance(to);
+        let steps = (distance / (NAV_CELL_SIZE * 0.5)).ceil().max(1.0) as usize;
+        for i in 0..=steps {
+            let point = from.lerp(to, i as f32 / steps as f32);
+            match self.world_to_cell(point) {
+                Some(cell) if self.is_walkable(cell) => {}
+                // Outside of the grid counts as open space
+                None => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    /// A* search from `start` to `goal`, returning world-space waypoints
+    /// (excluding the start position). Returns `None` if the goal is unreachable.
+    pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        let start_cell = self.nearest_walkable(self.world_to_cell(start)?, 2)?;
+        let goal_cell = self.nearest_walkable(self.world_to_cell(goal)?, 4)?;
+
+        let mut g_score = vec![f32::INFINITY; self.width * self.height];
+        let mut came_from: Vec<Option<UVec2>> = vec![None; self.width * self.height];
+        let mut open = BinaryHeap::new();
+
+        g_score[self.index(start_cell)] = 0.0;
+        open.push(OpenNode { cell: start_cell, f_score: octile(start_cell, goal_cell) });
+
+        while let Some(OpenNode { cell, f_score }) = open.pop() {
+            if cell == goal_cell {
+                let mut cells = vec![cell];
+                let mut current = cell;
+                while let Some(previous) = came_from[self.index(current)] {
+                    cells.push(previous);
+                    current = previous;
+                }
+                cells.reverse();
+                return Some(self.smooth_path(start, &cells, goal));
+            }
+
+            let current_g = g_score[self.index(cell)];
+            // Skip stale heap entries
+            if f_score > current_g + octile(cell, goal_cell) + f32::EPSILON {
+                continue;
+            }
+
+            for (neighbour, cost) in self.neighbours(cell) {
+                let tentative = current_g + cost;
+                let index = self.index(neighbour);
+                if tentative < g_score[index] {
+                    g_score[index] = tentative;
+                    came_from[index] = Some(cell);
+                    open.push(OpenNode {
+                        cell: neighbour,
+                        f_score: tentative + octile(neighbour, goal_cell),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    // 8-connected neighbours, diagonals only if both adjacent sides are open
+    fn neighbours(&self, cell: UVec2) -> Vec<(UVec2, f32)> {
+        let mut result = Vec::with_capacity(8);
+        let c = cell.as_ivec2();
+        let open = |offset: IVec2| {
+            let n = c + offset;
+            n.x >= 0 && n.y >= 0 && self.is_walkable(n.as_uvec2())
+        };
+
+        for (dx, dy) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+            if open(IVec2::new(dx, dy)) {
+                result.push(((c + IVec2::new(dx, dy)).as_uvec2(), STRAIGHT_COST));
+            }
+        }
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            if open(IVec2::new(dx, dy)) && open(IVec2::new(dx, 0)) && open(IVec2::new(0, dy)) {
+                result.push(((c + IVec2::new(dx, dy)).as_uvec2(), DIAGONAL_COST));
+            }
+        }
+        result
+    }
+
+    // Drop every waypoint that can be skipped with a straight line
+    fn smooth_path(&self, start: Vec2, cells: &[UVec2], goal: Vec2) -> Vec<Vec2> {
+        let mut points: Vec<Vec2> = cells.iter().map(|cell| self.cell_to_world(*cell)).collect();
+        if let Some(last) = points.last_mut() {
+            if self.has_line_of_sight(*last, goal) {
+                *last = goal;
+            }
+        }
+
+        let mut smoothed = Vec::new();
+        let mut anchor = start;
+        let mut i = 0;
+        while i < points.len() {
+            // Find the furthest point still visible from the anchor
+            let mut furthest = i;
+            for j in (i..points.len()).rev() {
+                if self.has_line_of_sight(anchor, points[j]) {
+                    furthest = j;
+                    break;
+                }
+            }
+            smoothed.push(points[furthest]);
+            anchor = points[furthest];
+            i = furthest + 1;
+        }
+        smoothed
+    }
+}
+
+fn octile(a: UVec2, b: UVec2) -> f32 {
+    let d = (a.as_ivec2() - b.as_ivec2()).abs();
+    let (min, max) = (d.x.min(d.y) as f32, d.x.max(d.y) as f32);
+    max * STRAIGHT_COST + min * (DIAGONAL_COST - STRAIGHT_COST)
+}
+
+// Entry of the A* open list, ordered so the BinaryHeap pops the lowest f-score
+#[derive(PartialEq)]
+struct OpenNode {
+    cell: UVec2,
+    f_score: f32,
+}
+
+impl Eq for OpenNode {}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f_score.total_cmp(&self.f_score)
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>()
+            .add_systems(Update, bake_nav_grid);
+    }
+}
+
+// Rebuild the grid whenever walls are added or removed
+fn bake_nav_grid(
+    mut nav_grid: ResMut<NavGrid>,
+    walls: Query<(&Collider, &Transform), With<Wall>>,
+    added_walls: Query<(), Added<Wall>>,
+    mut removed_walls: RemovedComponents<Wall>,
+) {
+    let removed = removed_walls.read().count() > 0;
+    if added_walls.is_empty() && !removed {
+        return;
+    }
+
+    let obstacles: Vec<(Vec2, Vec2)> = walls
+        .iter()
+        .filter_map(|(collider, transform)| {
+            let cuboid = collider.as_cuboid()?;
+            let half = cuboid.half_extents() * transform.scale.truncate();
+            Some((transform.translation.truncate(), half))
+        })
+        .collect();
+
+    *nav_grid = NavGrid::from_obstacles(&obstacles);
+    info!("Baked navigation grid from {} walls", obstacles.len());
+}
@@ -1,9 +1,12 @@
 use bevy::prelude::*;
+use crate::animation::AnimationLibrary;
+use crate::loading::RequiredAssets;
 
 #[derive(Resource)]
 pub struct OrcAssets {
     pub texture: Handle<Image>,
     pub atlas: Handle<TextureAtlasLayout>,
+    pub animations: Handle<AnimationLibrary>,
 }
 
 pub struct OrcAssetPlugin;
@@ -17,10 +20,12 @@ impl Plugin for OrcAssetPlugin {
 fn load_orc_assets(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let texture = asset_server.load("orc/Orc.png");
+    let texture = required.image(&asset_server, "orc/Orc.png", UVec2::new(800, 600));
     let layout = TextureAtlasLayout::from_grid(UVec2::new(100, 100), 8, 6, None, None);
     let atlas = layouts.add(layout);
-    commands.insert_resource(OrcAssets { texture, atlas });
+    let animations = required.load(&asset_server, "animations/orc.anim.ron");
+    commands.insert_resource(OrcAssets { texture, atlas, animations });
 }
\ No newline at end of file
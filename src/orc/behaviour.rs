@@ -0,0 +1,160 @@
+use std::sync::OnceLock;
+
+use bevy::prelude::*;
+use crate::ai::{self, BehaviourTree, Node, Status};
+use crate::orc::OrcState;
+use crate::orc::perception::TARGET_REACHED;
+
+const ORC_SPEED: f32 = 80.0;
+const ORC_WANDER_SPEED: f32 = 35.0;
+pub const ATTACK_RANGE: f32 = 90.0;
+pub const ATTACK_ANIM_DURATION: f32 = 0.5; // 5 frames at 10 FPS
+pub const ATTACK_COOLDOWN: f32 = 0.8;      // 1 second idle after attack
+
+/// Everything an orc's behaviour tree reads and writes during one tick
+pub struct OrcBlackboard {
+    // What the orc knows this frame
+    pub dt: f32,
+    pub position: Vec2,
+    pub player_pos: Vec2,
+    pub sees_player: bool,
+    pub last_seen: Option<Vec2>,
+    pub wander_target: Option<Vec2>,
+    pub surround_slot: Option<Vec2>,
+
+    // Carried between frames on `OrcEnemy`
+    pub attack_timer: f32,
+
+    // Decisions for the movement system to carry out
+    pub state: OrcState,
+    pub move_target: Option<Vec2>,
+    pub speed: f32,
+}
+
+/// The regular orc: attack when close, chase what it sees, search what it
+/// remembers and wander otherwise. Built on first use, every orc gets a clone of it.
+pub fn orc_behaviour_tree() -> BehaviourTree<OrcBlackboard> {
+    static TREE: OnceLock<BehaviourTree<OrcBlackboard>> = OnceLock::new();
+    TREE.get_or_init(build_orc_behaviour_tree).clone()
+}
+
+fn build_orc_behaviour_tree() -> BehaviourTree<OrcBlackboard> {
+    BehaviourTree::new(ai::sequence(vec![
+        ai::action("tick_attack_timer", tick_attack_timer),
+        ai::selector(vec![
+            attack_cycle(),
+            ai::sequence(vec![
+                ai::condition("sees_player", sees_player),
+                ai::selector(vec![
+                    ai::sequence(vec![
+                        ai::condition("player_in_attack_range", player_in_attack_range),
+                        ai::action("start_attack", start_attack),
+                    ]),
+                    ai::action("chase_player", chase_player),
+                ]),
+            ]),
+            ai::sequence(vec![
+                ai::condition("remembers_player", remembers_player),
+                ai::action("search_last_seen", search_last_seen),
+            ]),
+            ai::action("wander", wander),
+        ]),
+    ]))
+}
+
+/// Attack animation followed by a cooldown, shared by anything using the orc attack timings
+pub fn attack_cycle() -> Node<OrcBlackboard> {
+    ai::selector(vec![
+        ai::sequence(vec![
+            ai::condition("attack_animation_playing", attack_animation_playing),
+            ai::action("attack", attack),
+        ]),
+        ai::sequence(vec![
+            ai::condition("recovering_from_attack", recovering_from_attack),
+            ai::action("rest", rest),
+        ]),
+    ])
+}
+
+// ——— Conditions ———
+
+fn attack_animation_playing(bb: &OrcBlackboard) -> bool {
+    bb.attack_timer > ATTACK_COOLDOWN
+}
+
+fn recovering_from_attack(bb: &OrcBlackboard) -> bool {
+    bb.attack_timer > 0.0
+}
+
+fn sees_player(bb: &OrcBlackboard) -> bool {
+    bb.sees_player
+}
+
+fn player_in_attack_range(bb: &OrcBlackboard) -> bool {
+    bb.position.distance(bb.player_pos) <= ATTACK_RANGE
+}
+
+fn remembers_player(bb: &OrcBlackboard) -> bool {
+    bb.last_seen.is_some()
+}
+
+// ——— Actions ———
+
+fn tick_attack_timer(bb: &mut OrcBlackboard) -> Status {
+    let max_timer = ATTACK_ANIM_DURATION + ATTACK_COOLDOWN;
+    bb.attack_timer = (bb.attack_timer.min(max_timer) - bb.dt).max(0.0);
+    Status::Success
+}
+
+fn attack(bb: &mut OrcBlackboard) -> Status {
+    bb.move_target = None;
+    bb.state = OrcState::Attacking;
+    Status::Running
+}
+
+fn rest(bb: &mut OrcBlackboard) -> Status {
+    bb.move_target = None;
+    bb.state = OrcState::Idle;
+    Status::Running
+}
+
+fn start_attack(bb: &mut OrcBlackboard) -> Status {
+    bb.attack_timer = ATTACK_ANIM_DURATION + ATTACK_COOLDOWN;
+    attack(bb)
+}
+
+fn chase_player(bb: &mut OrcBlackboard) -> Status {
+    // Head for this orc's spot around the player
+    bb.move_target = Some(bb.surround_slot.unwrap_or(bb.player_pos));
+    bb.speed = ORC_SPEED;
+    bb.state = OrcState::Walking;
+    Status::Running
+}
+
+fn search_last_seen(bb: &mut OrcBlackboard) -> Status {
+    let Some(last_seen) = bb.last_seen else {
+        return Status::Failure;
+    };
+
+    // Go look where the player was last seen, then wait there
+    if bb.position.distance(last_seen) <= TARGET_REACHED {
+        return rest(bb);
+    }
+    bb.move_target = Some(last_seen);
+    bb.speed = ORC_SPEED;
+    bb.state = OrcState::Searching;
+    Status::Running
+}
+
+fn wander(bb: &mut OrcBlackboard) -> Status {
+    match bb.wander_target {
+        Some(target) => {
+            bb.move_target = Some(target);
+            bb.speed = ORC_WANDER_SPEED;
+            bb.state = OrcState::Wandering;
+            Status::Running
+        }
+        // Pausing between two wander targets
+        None => rest(bb),
+    }
+}
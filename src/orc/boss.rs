@@ -0,0 +1,452 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::player_code::{Health, Player};
+use crate::orc::{OrcEnemy, OrcState};
+use crate::orc::assets::OrcAssets;
+use crate::orc::collision::AttackHitbox;
+use crate::orc::behaviour::{ATTACK_ANIM_DURATION, ATTACK_COOLDOWN};
+use crate::orc::spawn::spawn_orc;
+use crate::level::LevelEntity;
+use crate::ysort::YSort;
+
+pub const BOSS_HEALTH: f32 = 200.0;
+const BOSS_DAMAGE: f32 = 2.0;
+const BOSS_SCALE: f32 = 9.0;
+const BOSS_FOOT_OFFSET: f32 = -9.0 * BOSS_SCALE; // Collider bottom, in texels below the center
+const BOSS_SPEED: f32 = 55.0;
+const BOSS_MELEE_RANGE: f32 = 140.0;
+
+// Special attacks
+const SPECIAL_INTERVAL_SECOND_PHASE: f32 = 6.0;
+const SPECIAL_INTERVAL_THIRD_PHASE: f32 = 4.0;
+const CHARGE_WINDUP: f32 = 0.6;     // Stand still and "aim" before charging
+const CHARGE_DURATION: f32 = 0.8;
+const CHARGE_SPEED: f32 = 420.0;
+const SLAM_TELEGRAPH: f32 = 1.0;    // How long the warning circle is shown
+const SLAM_ACTIVE: f32 = 0.2;       // How long the slam hitbox exists
+const SLAM_RADIUS: f32 = 160.0;
+const RECOVER_DURATION: f32 = 1.2;
+
+// Summoning
+const SUMMON_INTERVAL: f32 = 10.0;
+const SUMMON_RADIUS: f32 = 150.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BossPhase {
+    First,
+    Second,
+    Third,
+}
+
+impl BossPhase {
+    // Phases change at 2/3 and 1/3 of the health pool
+    pub fn from_health(health: f32, max_health: f32) -> Self {
+        let fraction = health / max_health;
+        if fraction > 2.0 / 3.0 {
+            BossPhase::First
+        } else if fraction > 1.0 / 3.0 {
+            BossPhase::Second
+        } else {
+            BossPhase::Third
+        }
+    }
+
+    // Number of regular orcs summoned when entering the phase (and periodically in the last one)
+    fn summon_count(self) -> usize {
+        match self {
+            BossPhase::First => 0,
+            BossPhase::Second => 2,
+            BossPhase::Third => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BossAction {
+    Chase,
+    ChargeWindup,
+    Charging,
+    SlamTelegraph,
+    Slamming,
+    Recover,
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub max_health: f32,
+    pub phase: BossPhase,
+    action: BossAction,
+    action_timer: Timer,
+    special_timer: Timer,
+    summon_timer: Timer,
+    charge_direction: Vec2,
+    next_special_is_slam: bool,
+    telegraph: Option<Entity>,
+}
+
+impl Boss {
+    pub fn new(max_health: f32) -> Self {
+        Self {
+            max_health,
+            phase: BossPhase::First,
+            action: BossAction::Chase,
+            action_timer: Timer::from_seconds(0.0, TimerMode::Once),
+            special_timer: Timer::from_seconds(SPECIAL_INTERVAL_SECOND_PHASE, TimerMode::Repeating),
+            summon_timer: Timer::from_seconds(SUMMON_INTERVAL, TimerMode::Repeating),
+            charge_direction: Vec2::ZERO,
+            next_special_is_slam: false,
+            telegraph: None,
+        }
+    }
+
+    /// The melee hitbox is live for the whole charge, not just the swing frames
+    pub fn is_charging(&self) -> bool {
+        self.action == BossAction::Charging
+    }
+
+    fn start_action(&mut self, action: BossAction, duration: f32) {
+        self.action = action;
+        self.action_timer = Timer::from_seconds(duration, TimerMode::Once);
+    }
+}
+
+// Warning circle drawn on the floor before a slam lands
+#[derive(Component)]
+struct SlamTelegraph {
+    timer: Timer,
+}
+
+// Short-lived area hitbox created when the slam lands
+#[derive(Component)]
+struct SlamHitbox {
+    timer: Timer,
+}
+
+// Event sent whenever the boss crosses a health threshold
+#[derive(Event)]
+pub struct BossPhaseChangedEvent {
+    pub boss: Entity,
+    pub phase: BossPhase,
+}
+
+pub struct OrcBossPlugin;
+impl Plugin for OrcBossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BossPhaseChangedEvent>()
+            .add_systems(Update, (
+                spawn_boss_on_click,
+                update_boss_phase,
+                boss_behaviour_system.after(update_boss_phase),
+                animate_slam_telegraphs,
+                despawn_slam_hitboxes,
+            ));
+    }
+}
+
+fn spawn_boss_on_click(
+    buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+    boss_q: Query<(), With<Boss>>,
+    mut commands: Commands,
+    orc_assets: Res<OrcAssets>,
+) {
+    // Only one boss at a time
+    if !buttons.just_pressed(MouseButton::Right) || !boss_q.is_empty() {
+        return;
+    }
+
+    let window = window_query.single();
+    if let Some(screen_pos) = window.cursor_position() {
+        if let Ok((camera, cam_tf)) = camera_q.get_single() {
+            if let Ok(world_ray) = camera.viewport_to_world(cam_tf, screen_pos) {
+                let spawn_pos = world_ray.origin.truncate().extend(0.0);
+                spawn_boss(&mut commands, &orc_assets, spawn_pos);
+            }
+        }
+    }
+}
+
+pub(crate) fn spawn_boss(
+    commands: &mut Commands,
+    assets: &OrcAssets,
+    spawn_pos: Vec3,
+) -> Entity {
+    // Start from a regular orc and override what makes the boss different
+    let boss_entity = spawn_orc(commands, assets, spawn_pos);
+
+    commands.entity(boss_entity).insert((
+        Sprite {
+            image: assets.texture.clone(),
+            texture_atlas: Some(TextureAtlas {
+                layout: assets.atlas.clone(),
+                index: 0,
+            }),
+            color: Color::srgb(1.0, 0.6, 0.6), // Reddish tint to tell it apart
+            ..default()
+        },
+        Transform::from_translation(spawn_pos).with_scale(Vec3::splat(BOSS_SCALE)),
+        YSort { offset: BOSS_FOOT_OFFSET },
+        OrcEnemy::new(BOSS_HEALTH, BOSS_DAMAGE),
+        Health::new(BOSS_HEALTH),
+        Boss::new(BOSS_HEALTH),
+        Name::new("OrcBoss"),
+    ));
+
+    info!("Spawned orc boss {:?} at {:?}", boss_entity, spawn_pos);
+    boss_entity
+}
+
+/// Spawn a boss that already lost `health`, straight into the matching phase
+/// so it doesn't summon the reinforcements of the phases it went through
+pub(crate) fn restore_boss(
+    commands: &mut Commands,
+    assets: &OrcAssets,
+    spawn_pos: Vec3,
+    health: f32,
+    enemy: OrcEnemy,
+) -> Entity {
+    let boss_entity = spawn_boss(commands, assets, spawn_pos);
+
+    let mut boss = Boss::new(BOSS_HEALTH);
+    boss.phase = BossPhase::from_health(health, BOSS_HEALTH);
+    if boss.phase == BossPhase::Third {
+        boss.special_timer = Timer::from_seconds(SPECIAL_INTERVAL_THIRD_PHASE, TimerMode::Repeating);
+    }
+    commands.entity(boss_entity).insert((boss, enemy, Health::new(health)));
+    boss_entity
+}
+
+// Check health thresholds and summon reinforcements on phase changes
+fn update_boss_phase(
+    mut commands: Commands,
+    mut boss_q: Query<(Entity, &Transform, &Health, &OrcEnemy, &mut Boss)>,
+    mut phase_events: EventWriter<BossPhaseChangedEvent>,
+    orc_assets: Res<OrcAssets>,
+    time: Res<Time>,
+) {
+    for (boss_entity, transform, health, orc, mut boss) in boss_q.iter_mut() {
+        if orc.state == OrcState::Dying {
+            continue;
+        }
+
+        let phase = BossPhase::from_health(health.health, boss.max_health);
+        if phase != boss.phase {
+            boss.phase = phase;
+            let interval = match phase {
+                BossPhase::Third => SPECIAL_INTERVAL_THIRD_PHASE,
+                _ => SPECIAL_INTERVAL_SECOND_PHASE,
+            };
+            boss.special_timer = Timer::from_seconds(interval, TimerMode::Repeating);
+
+            summon_orcs(&mut commands, &orc_assets, transform.translation, phase.summon_count());
+            phase_events.send(BossPhaseChangedEvent { boss: boss_entity, phase });
+            info!("Orc boss {:?} entered phase {:?}", boss_entity, phase);
+        }
+
+        // The last phase keeps calling for help
+        if boss.phase == BossPhase::Third {
+            boss.summon_timer.tick(time.delta());
+            if boss.summon_timer.just_finished() {
+                summon_orcs(&mut commands, &orc_assets, transform.translation, phase.summon_count());
+            }
+        }
+    }
+}
+
+fn summon_orcs(commands: &mut Commands, assets: &OrcAssets, center: Vec3, count: usize) {
+    for i in 0..count {
+        // Spread the summoned orcs evenly on a circle around the boss
+        let angle = std::f32::consts::TAU * i as f32 / count as f32;
+        let offset = Vec2::from_angle(angle) * SUMMON_RADIUS;
+        spawn_orc(commands, assets, center + offset.extend(0.0));
+    }
+}
+
+fn boss_behaviour_system(
+    mut commands: Commands,
+    mut boss_q: Query<(Entity, &Transform, &mut Velocity, &mut OrcEnemy, &mut Boss, &mut Sprite)>,
+    player_q: Query<&Transform, With<Player>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    let player_pos = if let Ok(tf) = player_q.get_single() {
+        tf.translation.truncate()
+    } else {
+        return;
+    };
+
+    let dt = time.delta_secs();
+
+    for (boss_entity, transform, mut vel, mut orc, mut boss, mut sprite) in boss_q.iter_mut() {
+        if orc.state == OrcState::Dying {
+            vel.linvel = Vec2::ZERO;
+            if let Some(telegraph) = boss.telegraph.take() {
+                commands.entity(telegraph).despawn();
+            }
+            continue;
+        }
+
+        let boss_pos = transform.translation.truncate();
+        let to_player = player_pos - boss_pos;
+
+        boss.action_timer.tick(time.delta());
+        let action_finished = boss.action_timer.finished();
+
+        match boss.action {
+            BossAction::Chase => {
+                if to_player.x.abs() > 5.0 {
+                    sprite.flip_x = to_player.x < 0.0;
+                }
+
+                // Special attacks unlock after the first phase
+                if boss.phase != BossPhase::First {
+                    boss.special_timer.tick(time.delta());
+                    if boss.special_timer.just_finished() && orc.attack_cooldown_timer == 0.0 {
+                        vel.linvel = Vec2::ZERO;
+                        orc.state = OrcState::Idle;
+
+                        if boss.phase == BossPhase::Third && boss.next_special_is_slam {
+                            let telegraph = spawn_slam_telegraph(
+                                &mut commands,
+                                &mut meshes,
+                                &mut materials,
+                                boss_pos,
+                            );
+                            boss.telegraph = Some(telegraph);
+                            boss.start_action(BossAction::SlamTelegraph, SLAM_TELEGRAPH);
+                        } else {
+                            boss.start_action(BossAction::ChargeWindup, CHARGE_WINDUP);
+                        }
+                        boss.next_special_is_slam = !boss.next_special_is_slam;
+                        continue;
+                    }
+                }
+
+                // Regular melee cadence, same timings as a normal orc
+                orc.attack_cooldown_timer = (orc.attack_cooldown_timer - dt).max(0.0);
+                let timer = orc.attack_cooldown_timer;
+                if timer > ATTACK_COOLDOWN {
+                    vel.linvel = Vec2::ZERO;
+                    orc.state = OrcState::Attacking;
+                } else if timer > 0.0 {
+                    vel.linvel = Vec2::ZERO;
+                    orc.state = OrcState::Idle;
+                } else if to_player.length() <= BOSS_MELEE_RANGE {
+                    orc.attack_cooldown_timer = ATTACK_ANIM_DURATION + ATTACK_COOLDOWN;
+                    vel.linvel = Vec2::ZERO;
+                    orc.state = OrcState::Attacking;
+                } else {
+                    vel.linvel = to_player.normalize_or_zero() * BOSS_SPEED;
+                    orc.state = OrcState::Walking;
+                }
+            }
+            BossAction::ChargeWindup => {
+                // Keep aiming at the player until the charge starts
+                vel.linvel = Vec2::ZERO;
+                orc.state = OrcState::Idle;
+                boss.charge_direction = to_player.normalize_or_zero();
+                if action_finished {
+                    boss.start_action(BossAction::Charging, CHARGE_DURATION);
+                }
+            }
+            BossAction::Charging => {
+                // The melee hitbox is live for the whole charge
+                vel.linvel = boss.charge_direction * CHARGE_SPEED;
+                orc.state = OrcState::Attacking;
+                if action_finished {
+                    vel.linvel = Vec2::ZERO;
+                    boss.start_action(BossAction::Recover, RECOVER_DURATION);
+                }
+            }
+            BossAction::SlamTelegraph => {
+                vel.linvel = Vec2::ZERO;
+                orc.state = OrcState::Idle;
+                if action_finished {
+                    if let Some(telegraph) = boss.telegraph.take() {
+                        commands.entity(telegraph).despawn();
+                    }
+
+                    // Only now does the area hitbox exist
+                    commands.spawn((
+                        Collider::ball(SLAM_RADIUS),
+                        Sensor,
+                        ActiveEvents::COLLISION_EVENTS,
+                        AttackHitbox::area(boss_entity),
+                        SlamHitbox {
+                            timer: Timer::from_seconds(SLAM_ACTIVE, TimerMode::Once),
+                        },
+                        Transform::from_translation(boss_pos.extend(0.0)),
+                        Name::new("BossSlamHitbox"),
+                        LevelEntity,
+                    ));
+                    orc.state = OrcState::Attacking;
+                    boss.start_action(BossAction::Slamming, SLAM_ACTIVE);
+                }
+            }
+            BossAction::Slamming => {
+                vel.linvel = Vec2::ZERO;
+                orc.state = OrcState::Attacking;
+                if action_finished {
+                    boss.start_action(BossAction::Recover, RECOVER_DURATION);
+                }
+            }
+            BossAction::Recover => {
+                // Vulnerable window after a special attack
+                vel.linvel = Vec2::ZERO;
+                orc.state = OrcState::Idle;
+                if action_finished {
+                    boss.action = BossAction::Chase;
+                }
+            }
+        }
+    }
+}
+
+fn spawn_slam_telegraph(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+    position: Vec2,
+) -> Entity {
+    commands.spawn((
+        Mesh2d(meshes.add(Circle::new(SLAM_RADIUS))),
+        MeshMaterial2d(materials.add(Color::srgba(0.9, 0.1, 0.1, 0.0))),
+        Transform::from_translation(position.extend(-0.5)), // Drawn on the floor, under the characters
+        SlamTelegraph {
+            timer: Timer::from_seconds(SLAM_TELEGRAPH, TimerMode::Once),
+        },
+        Name::new("BossSlamTelegraph"),
+        LevelEntity,
+    )).id()
+}
+
+// Fade the warning circle in so the player can see the slam coming
+fn animate_slam_telegraphs(
+    time: Res<Time>,
+    mut query: Query<(&mut SlamTelegraph, &MeshMaterial2d<ColorMaterial>)>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for (mut telegraph, material) in query.iter_mut() {
+        telegraph.timer.tick(time.delta());
+        if let Some(material) = materials.get_mut(&material.0) {
+            let progress = telegraph.timer.fraction();
+            material.color.set_alpha(0.15 + progress * 0.45);
+        }
+    }
+}
+
+fn despawn_slam_hitboxes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SlamHitbox)>,
+) {
+    for (entity, mut slam) in query.iter_mut() {
+        slam.timer.tick(time.delta());
+        if slam.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -1,13 +1,34 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use crate::animation::AnimationConfig;
 use crate::orc::{OrcEnemy, OrcState};
-use crate::player_code::{Health, Player};
-use crate::shield::DamageEvent;
+use crate::orc::boss::Boss;
+use crate::player_code::{Player, PlayerDamageEvent};
+
+/// Animation window of the attack clip in which a melee swing can hurt
+pub const HIT_WINDOW: &str = "hit";
 
 /// Marker component for the attack‐hitbox sensor attached to each Orc
 #[derive(Component)]
 pub struct AttackHitbox {
     pub owner: Entity,
+    /// Only hurts during the owner's `HIT_WINDOW` frames, otherwise for as
+    /// long as the owner attacks
+    pub frame_gated: bool,
+    // The current attack already hurt the player
+    landed: bool,
+}
+
+impl AttackHitbox {
+    /// Weapon swing, follows the attack animation
+    pub fn melee(owner: Entity) -> Self {
+        Self { owner, frame_gated: true, landed: false }
+    }
+
+    /// Area attack that is dangerous for its whole lifetime
+    pub fn area(owner: Entity) -> Self {
+        Self { owner, frame_gated: false, landed: false }
+    }
 }
 
 #[derive(Component)]
@@ -76,7 +97,7 @@ fn spawn_attack_hitboxes(
                 Collider::convex_hull(&points).unwrap_or(Collider::ball(radius)),
                 Sensor,
                 ActiveEvents::COLLISION_EVENTS,
-                AttackHitbox { owner: orc },
+                AttackHitbox::melee(orc),
                 // Make the hitbox start at the center of the orc
                 Transform::from_xyz(0.0, 0.0, 10.0),
             ));
@@ -132,58 +153,44 @@ fn update_attack_hitboxes(
     }
 }
 
+// A swing only hurts while the attack clip shows its "hit" frames, see
+// animations/orc.anim.ron, and lands at most once
 fn orc_player_collision(
-    mut damage_events: EventWriter<DamageEvent>,
-    mut health_q: Query<&mut Health, With<Player>>,
-    mut events: EventReader<CollisionEvent>,
-    hitbox_q: Query<(&AttackHitbox, &GlobalTransform)>,
-    orc_state_q: Query<(&OrcEnemy, &Transform)>,
-    _player_tf_q: Query<&GlobalTransform, With<Player>>,
+    mut damage_events: EventWriter<PlayerDamageEvent>,
+    rapier_context: ReadRapierContext,
+    mut hitbox_q: Query<(Entity, &mut AttackHitbox)>,
+    orc_q: Query<(&OrcEnemy, Option<&AnimationConfig>, Option<&Boss>)>,
     player_ent_q: Query<Entity, With<Player>>,
 ) {
-    let player = match player_ent_q.get_single() {
-        Ok(e) => e,
-        Err(_) => return,
-    };
-    let health = match health_q.get_single_mut() {
-        Ok(h) => h,
-        Err(_) => return,
+    let Ok(player) = player_ent_q.get_single() else {
+        return;
     };
-
-    for collision_event in events.read() {
-        let (a, b) = match collision_event {
-            CollisionEvent::Started(a, b, _) => (*a, *b),
-            _ => continue,
+    let rapier_context = rapier_context.single();
+
+    for (hitbox_entity, mut hitbox) in hitbox_q.iter_mut() {
+        let live = match orc_q.get(hitbox.owner) {
+            Ok((orc, config, boss)) => {
+                orc.state == OrcState::Attacking
+                    && (!hitbox.frame_gated
+                        || boss.is_some_and(Boss::is_charging)
+                        || config.is_some_and(|config| config.in_window(HIT_WINDOW)))
+            }
+            Err(_) => false,
         };
-
-        // Identify which is hitbox & which is player
-        let (hitbox_entity, _player_entity) = if a == player {
-            (b, a)
-        } else if b == player {
-            (a, b)
-        } else {
+        if !live {
+            hitbox.landed = false;
             continue;
-        };
-
-        // Pull out the hitbox component & its world position
-        let (hitbox, _) = match hitbox_q.get(hitbox_entity) {
-            Ok(pair) => pair,
-            Err(_) => continue,
-        };
-
-        // Only when Orc is currently Attacking
-        let (orc, _) = match orc_state_q.get(hitbox.owner) {
-            Ok(pair) if pair.0.state == OrcState::Attacking => pair,
-            _ => continue,
-        };
+        }
+        if hitbox.landed {
+            continue;
+        }
 
-        // We've already checked the sector with our collider shape,
-        // so if there's a collision, the player is in the attack zone
-        //health.health -= orc.damage;
-        damage_events.send(DamageEvent{amount: 1.0});
-        info!(
-            "Player hit by Orc {:?}! New health: {:.1}",
-            hitbox.owner, health.health
-        );
+        // Overlap rather than collision start, the player may already stand
+        // in reach when the swing comes down
+        if rapier_context.intersection_pair(hitbox_entity, player) == Some(true) {
+            hitbox.landed = true;
+            damage_events.send(PlayerDamageEvent { damage: 1.0 });
+            info!("Player hit by Orc {:?}!", hitbox.owner);
+        }
     }
-}
\ No newline at end of file
+}
@@ -2,26 +2,38 @@ mod assets;
 mod spawn;
 mod movement;
 mod sprite;
+mod steering;
+mod pathfinding;
+mod perception;
+pub mod behaviour;
 pub mod collision;
+pub mod boss;
 
-pub use assets::OrcAssetPlugin;
+pub use assets::{OrcAssetPlugin, OrcAssets};
 pub use spawn::OrcSpawnPlugin;
+pub(crate) use spawn::restore_orc;
 pub use movement::OrcMovementPlugin;
 pub use sprite::OrcSpritePlugin;
 pub use collision::OrcCollisionPlugin;
+pub use boss::OrcBossPlugin;
+pub use pathfinding::OrcPathfindingPlugin;
+pub use perception::{OrcPerception, OrcPerceptionPlugin};
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum OrcState {
     Idle,
     Walking,
+    Wandering,  // Strolling around while the player is not perceived
+    Searching,  // Heading to where the player was last seen
     Attacking,
     Hurt,
     Dying,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Clone, Debug, Serialize, Deserialize)]
 pub struct OrcEnemy {
     pub health: f32,
     pub damage: f32,
@@ -35,7 +47,7 @@ impl OrcEnemy {
             health,
             damage,
             state: OrcState::Idle,
-            attack_cooldown_timer: 1.0,
+            attack_cooldown_timer: 0.0, // Ready to swing as soon as it reaches the player
         }
     }
 }
@@ -47,9 +59,12 @@ impl Plugin for OrcPlugin {
         app
             .add_plugins(OrcAssetPlugin)
             .add_plugins(OrcSpawnPlugin)
+            .add_plugins(OrcPerceptionPlugin)
+            .add_plugins(OrcPathfindingPlugin)
             .add_plugins(OrcMovementPlugin)
             .add_plugins(OrcSpritePlugin)
-            .add_plugins(OrcCollisionPlugin);
+            .add_plugins(OrcCollisionPlugin)
+            .add_plugins(OrcBossPlugin);
         
         println!("OrcPlugin initialized with all sub-plugins");
     }
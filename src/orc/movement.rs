@@ -1,35 +1,35 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use crate::ai::{AiSet, BehaviourTree};
 use crate::player_code::Player;
 use crate::orc::{OrcEnemy, OrcState};
-
-const ORC_SPEED: f32 = 80.0;
-pub const ATTACK_RANGE: f32 = 90.0;
-pub const ATTACK_ANIM_DURATION: f32 = 0.5; // 5 frames at 10 FPS
-const ATTACK_COOLDOWN: f32 = 0.8;      // 1 second idle after attack
+use crate::orc::behaviour::OrcBlackboard;
+use crate::orc::boss::Boss;
+use crate::orc::steering;
+use crate::orc::pathfinding::OrcPath;
+use crate::orc::perception::OrcPerception;
 
 pub struct OrcMovementPlugin;
 impl Plugin for OrcMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, orc_movement_system)
-           .add_systems(Update, orc_init_system);
-    }
-}
-
-// New system to ensure orcs start with proper initial state
-fn orc_init_system(
-    mut query: Query<(&mut OrcEnemy, &mut Sprite), Added<OrcEnemy>>,
-) {
-    for (mut orc, _sprite) in query.iter_mut() {
-        // Newly spawned orcs start in idle state
-        orc.state = OrcState::Idle;
-        orc.attack_cooldown_timer = 0.0;
+        app.add_systems(Update, orc_movement_system.in_set(AiSet::Think));
     }
 }
 
+// Tick each orc's behaviour tree and carry out what it decided
 fn orc_movement_system(
-    mut query: Query<(&Transform, &mut Velocity, &mut OrcEnemy, &mut Sprite)>,
+    mut query: Query<(
+        Entity,
+        &Transform,
+        &mut Velocity,
+        &mut OrcEnemy,
+        &mut Sprite,
+        &mut BehaviourTree<OrcBlackboard>,
+        &OrcPerception,
+        Option<&OrcPath>,
+    ), Without<Boss>>,
     player_q: Query<&Transform, With<Player>>,
+    rapier_context: ReadRapierContext,
     time: Res<Time>,
 ) {
     // Get player position
@@ -39,54 +39,68 @@ fn orc_movement_system(
         return;
     };
 
-    let dt = time.delta_secs();
-    let max_timer = ATTACK_ANIM_DURATION + ATTACK_COOLDOWN;
+    let rapier_context = rapier_context.single();
+
+    // Snapshot of every living orc for separation, and of the ones that can
+    // see the player for surround slots
+    let orcs: Vec<(Entity, Vec2)> = query
+        .iter()
+        .filter(|(_, _, _, orc, _, _, _, _)| orc.state != OrcState::Dying)
+        .map(|(entity, transform, _, _, _, _, _, _)| (entity, transform.translation.truncate()))
+        .collect();
+    let chasers: Vec<(Entity, Vec2)> = query
+        .iter()
+        .filter(|(_, _, _, orc, _, _, perception, _)| orc.state != OrcState::Dying && perception.sees_player)
+        .map(|(entity, transform, _, _, _, _, _, _)| (entity, transform.translation.truncate()))
+        .collect();
+    let slots = steering::surround_slots(player_pos, &chasers);
 
-    for (transform, mut vel, mut orc, mut sprite) in query.iter_mut() {
+    for (entity, transform, mut vel, mut orc, mut sprite, mut tree, perception, path) in query.iter_mut() {
         if orc.state == OrcState::Dying {
                 vel.linvel = Vec2::ZERO;
                 continue;
         }
 
         let orc_pos = transform.translation.truncate();
-        let to_player = player_pos - orc_pos;
-        let dist = to_player.length();
-
-        // Flip sprite based on horizontal direction threshold
-        if to_player.x.abs() > 5.0 {
-            sprite.flip_x = to_player.x < 0.0;
-        }
+        let mut blackboard = OrcBlackboard {
+            dt: time.delta_secs(),
+            position: orc_pos,
+            player_pos,
+            sees_player: perception.sees_player,
+            last_seen: perception.last_seen,
+            wander_target: perception.wander_target,
+            surround_slot: slots.get(&entity).copied(),
+            attack_timer: orc.attack_cooldown_timer,
+            state: OrcState::Idle,
+            move_target: None,
+            speed: 0.0,
+        };
+        tree.tick(&mut blackboard);
 
-        // Clamp and decrement timer
-        orc.attack_cooldown_timer = orc.attack_cooldown_timer.min(max_timer);
-        orc.attack_cooldown_timer = (orc.attack_cooldown_timer - dt).max(0.0);
-        let timer = orc.attack_cooldown_timer;
+        orc.attack_cooldown_timer = blackboard.attack_timer;
+        orc.state = blackboard.state;
 
-        // 1) If in attack animation phase (timer > cooldown)
-        if timer > ATTACK_COOLDOWN {
-            vel.linvel = Vec2::ZERO;
-            orc.state = OrcState::Attacking;
-            continue;
+        // Face the player while fighting, otherwise where we're going
+        let look = if perception.sees_player {
+            player_pos - orc_pos
+        } else {
+            blackboard.move_target.map_or(Vec2::ZERO, |target| target - orc_pos)
+        };
+        if look.x.abs() > 5.0 {
+            sprite.flip_x = look.x < 0.0;
         }
 
-        // 2) If in cooldown phase (0 < timer <= cooldown)
-        if timer > 0.0 {
+        // Follow the planned path while the target is behind walls
+        let Some(target) = blackboard.move_target else {
             vel.linvel = Vec2::ZERO;
-            orc.state = OrcState::Idle;
             continue;
-        }
+        };
+        let target = path.and_then(|path| path.next_waypoint()).unwrap_or(target);
 
-        // 3) Timer == 0: decide new action based on distance
-        if dist <= ATTACK_RANGE {
-            // Start new attack cycle
-            orc.attack_cooldown_timer = max_timer;
-            vel.linvel = Vec2::ZERO;
-            orc.state = OrcState::Attacking;
-        } else {
-            // Chase player
-            let dir = to_player.normalize_or_zero();
-            vel.linvel = dir * ORC_SPEED;
-            orc.state = OrcState::Walking;
-        }
+        // Keep clear of other orcs and walls on the way
+        let to_target = target - orc_pos;
+        let separation = steering::separation(entity, orc_pos, &orcs);
+        let avoidance = steering::wall_avoidance(&rapier_context, orc_pos, to_target.normalize_or_zero());
+        vel.linvel = steering::steer(to_target, separation, avoidance, blackboard.speed);
     }
-}
\ No newline at end of file
+}
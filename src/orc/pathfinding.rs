@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use crate::ai::AiSet;
+use crate::navigation::{NavGrid, NAV_CELL_SIZE};
+use crate::player_code::Player;
+use crate::orc::{OrcEnemy, OrcState};
+use crate::orc::perception::{OrcPerception, update_orc_perception};
+
+const REPLAN_INTERVAL: f32 = 0.5;   // Minimum time between two A* searches per orc
+const WAYPOINT_REACHED: f32 = NAV_CELL_SIZE * 0.5;
+
+/// Cached path from an orc to whatever it is hunting
+#[derive(Component)]
+pub struct OrcPath {
+    waypoints: Vec<Vec2>,
+    goal_cell: Option<UVec2>,
+    replan_timer: Timer,
+}
+
+impl Default for OrcPath {
+    fn default() -> Self {
+        Self {
+            waypoints: Vec::new(),
+            goal_cell: None,
+            replan_timer: Timer::from_seconds(REPLAN_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+impl OrcPath {
+    pub fn next_waypoint(&self) -> Option<Vec2> {
+        self.waypoints.first().copied()
+    }
+}
+
+pub struct OrcPathfindingPlugin;
+impl Plugin for OrcPathfindingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_orc_paths.after(update_orc_perception).in_set(AiSet::Sense));
+    }
+}
+
+// Re-plan periodically when the hunted position changed cell, and pop reached waypoints
+fn update_orc_paths(
+    nav_grid: Res<NavGrid>,
+    player_q: Query<&Transform, With<Player>>,
+    mut query: Query<(&Transform, &OrcEnemy, &OrcPerception, &mut OrcPath)>,
+    time: Res<Time>,
+) {
+    let player_pos = match player_q.get_single() {
+        Ok(tf) => tf.translation.truncate(),
+        Err(_) => return,
+    };
+    if nav_grid.is_empty() {
+        return;
+    }
+
+    for (transform, orc, perception, mut path) in query.iter_mut() {
+        // Wandering orcs don't plan, they just pick reachable spots
+        let goal = match perception.hunt_target(player_pos) {
+            Some(goal) if orc.state != OrcState::Dying => goal,
+            _ => {
+                path.waypoints.clear();
+                path.goal_cell = None;
+                continue;
+            }
+        };
+
+        let orc_pos = transform.translation.truncate();
+        path.replan_timer.tick(time.delta());
+
+        // Nothing to plan around if the goal is in plain sight
+        if nav_grid.has_line_of_sight(orc_pos, goal) {
+            path.waypoints.clear();
+            path.goal_cell = None;
+            continue;
+        }
+
+        // Plan right away the first time, afterwards only when the cached path is stale
+        let goal_cell = nav_grid.world_to_cell(goal);
+        let goal_moved = path.goal_cell != goal_cell;
+        let stale = path.replan_timer.finished() && (goal_moved || path.waypoints.is_empty());
+        if path.goal_cell.is_none() || stale {
+            path.waypoints = nav_grid.find_path(orc_pos, goal).unwrap_or_default();
+            path.goal_cell = goal_cell;
+        }
+
+        // Advance along the path
+        while let Some(waypoint) = path.next_waypoint() {
+            if orc_pos.distance(waypoint) > WAYPOINT_REACHED {
+                break;
+            }
+            path.waypoints.remove(0);
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::navigation::NavGrid;
+use crate::player_code::{Health, Player};
+use crate::orc::{OrcEnemy, OrcState};
+use crate::rng::GameRng;
+use crate::ai::AiSet;
+
+pub const AGGRO_RADIUS: f32 = 450.0;
+const LOSE_RADIUS: f32 = 650.0;      // Once aggroed, the player has to get this far to escape
+const MEMORY_DURATION: f32 = 4.0;    // How long an orc keeps hunting the last seen position
+const WANDER_RADIUS: f32 = 150.0;
+const WANDER_PAUSE_MIN: f32 = 1.5;
+const WANDER_PAUSE_MAX: f32 = 3.5;
+const WANDER_GIVE_UP: f32 = 5.0;     // Drop wander targets that can't be reached in time
+pub const TARGET_REACHED: f32 = 20.0;
+
+/// What an orc knows about the player
+#[derive(Component)]
+pub struct OrcPerception {
+    pub sees_player: bool,
+    pub last_seen: Option<Vec2>,
+    pub wander_target: Option<Vec2>,
+    home: Option<Vec2>,
+    memory_timer: Timer,
+    wander_timer: Timer,
+}
+
+impl Default for OrcPerception {
+    fn default() -> Self {
+        Self {
+            sees_player: false,
+            last_seen: None,
+            wander_target: None,
+            home: None,
+            memory_timer: Timer::from_seconds(MEMORY_DURATION, TimerMode::Once),
+            wander_timer: Timer::from_seconds(WANDER_PAUSE_MIN, TimerMode::Once),
+        }
+    }
+}
+
+impl OrcPerception {
+    /// Where the orc is trying to get to when hunting: the player, or where it was last seen
+    pub fn hunt_target(&self, player_pos: Vec2) -> Option<Vec2> {
+        if self.sees_player {
+            Some(player_pos)
+        } else {
+            self.last_seen
+        }
+    }
+
+    fn remember(&mut self, player_pos: Vec2) {
+        self.last_seen = Some(player_pos);
+        self.memory_timer.reset();
+        self.wander_target = None;
+    }
+}
+
+pub struct OrcPerceptionPlugin;
+impl Plugin for OrcPerceptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (update_orc_perception, update_orc_wander_targets).in_set(AiSet::Sense));
+    }
+}
+
+pub(super) fn update_orc_perception(
+    mut query: Query<(&Transform, &OrcEnemy, Ref<Health>, &mut OrcPerception)>,
+    player_q: Query<&Transform, With<Player>>,
+    rapier_context: ReadRapierContext,
+    time: Res<Time>,
+) {
+    let player_pos = match player_q.get_single() {
+        Ok(tf) => tf.translation.truncate(),
+        Err(_) => return,
+    };
+    let rapier_context = rapier_context.single();
+
+    for (transform, orc, health, mut perception) in query.iter_mut() {
+        if orc.state == OrcState::Dying {
+            perception.sees_player = false;
+            continue;
+        }
+
+        let orc_pos = transform.translation.truncate();
+        let to_player = player_pos - orc_pos;
+        let dist = to_player.length();
+
+        // Keep tracking an already aggroed player a bit further than the aggro radius
+        let radius = if perception.sees_player { LOSE_RADIUS } else { AGGRO_RADIUS };
+        let in_range = dist <= radius;
+
+        // Walls block the view
+        let visible = in_range && {
+            let filter = QueryFilter::only_fixed().exclude_sensors();
+            match rapier_context.cast_ray(orc_pos, to_player.normalize_or_zero(), dist, true, filter) {
+                Some((_, toi)) => toi >= dist,
+                None => true,
+            }
+        };
+
+        // Getting hit gives the player's position away, even from the dark
+        let was_hurt = health.is_changed() && !health.is_added();
+
+        perception.sees_player = visible;
+        if visible || was_hurt {
+            perception.remember(player_pos);
+            continue;
+        }
+
+        // Forget the player after a while
+        if perception.last_seen.is_some() {
+            perception.memory_timer.tick(time.delta());
+            if perception.memory_timer.finished() {
+                perception.last_seen = None;
+            }
+        }
+    }
+}
+
+// Pick random spots around the orc's spawn point while it has nothing to hunt
+fn update_orc_wander_targets(
+    mut query: Query<(&Transform, &OrcEnemy, &mut OrcPerception)>,
+    nav_grid: Res<NavGrid>,
+    mut rng: ResMut<GameRng>,
+    time: Res<Time>,
+) {
+    for (transform, orc, mut perception) in query.iter_mut() {
+        let orc_pos = transform.translation.truncate();
+        let home = *perception.home.get_or_insert(orc_pos);
+
+        if orc.state == OrcState::Dying || perception.sees_player || perception.last_seen.is_some() {
+            continue;
+        }
+
+        // Arrived (or got stuck), take a break before picking the next spot
+        if let Some(target) = perception.wander_target {
+            perception.wander_timer.tick(time.delta());
+            if orc_pos.distance(target) <= TARGET_REACHED || perception.wander_timer.finished() {
+                perception.wander_target = None;
+                let pause = rng.rng.gen_range(WANDER_PAUSE_MIN..WANDER_PAUSE_MAX);
+                perception.wander_timer = Timer::from_seconds(pause, TimerMode::Once);
+            }
+            continue;
+        }
+
+        perception.wander_timer.tick(time.delta());
+        if !perception.wander_timer.finished() {
+            continue;
+        }
+
+        let angle = rng.rng.gen_range(0.0..std::f32::consts::TAU);
+        let distance = rng.rng.gen_range(0.3..1.0) * WANDER_RADIUS;
+        let target = home + Vec2::from_angle(angle) * distance;
+
+        // Don't wander into walls, try again next frame instead
+        let walkable = nav_grid.is_empty()
+            || nav_grid.world_to_cell(target).is_some_and(|cell| nav_grid.is_walkable(cell));
+        if walkable {
+            perception.wander_target = Some(target);
+            perception.wander_timer = Timer::from_seconds(WANDER_GIVE_UP, TimerMode::Once);
+        }
+    }
+}
@@ -1,15 +1,23 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::animation::AnimationConfig;
+use crate::animation::AnimationStateMachine;
 use crate::player_code::Health;
 use crate::orc::assets::OrcAssets;
 use crate::orc::OrcEnemy;
+use crate::orc::pathfinding::OrcPath;
+use crate::orc::perception::OrcPerception;
+use crate::orc::behaviour::orc_behaviour_tree;
+use crate::level::{CurrentLevel, LevelEntity, OrcSpawnPoint};
+use crate::ysort::YSort;
+
+/// Distance from an orc's center down to its feet, for y-sorting
+const ORC_FOOT_OFFSET: f32 = -45.0;
 
 pub struct OrcSpawnPlugin;
 impl Plugin for OrcSpawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, spawn_orc_on_click);
+        app.add_systems(Update, (spawn_orc_on_click, spawn_orcs_at_spawn_points));
     }
 }
 
@@ -33,11 +41,27 @@ fn spawn_orc_on_click(
     }
 }
 
-fn spawn_orc(
+// Populate the level with the orcs placed in it. A level restored from a
+// save brings back the orcs that were alive instead.
+fn spawn_orcs_at_spawn_points(
+    mut commands: Commands,
+    spawn_points: Query<&Transform, Added<OrcSpawnPoint>>,
+    orc_assets: Res<OrcAssets>,
+    current_level: Res<CurrentLevel>,
+) {
+    if current_level.from_save {
+        return;
+    }
+    for spawn_point in spawn_points.iter() {
+        spawn_orc(&mut commands, &orc_assets, spawn_point.translation);
+    }
+}
+
+pub(crate) fn spawn_orc(
     commands: &mut Commands,
     assets: &OrcAssets,
     spawn_pos: Vec3,
-) {
+) -> Entity {
     let orc_entity = commands.spawn((
         // Visual components
         Sprite {
@@ -48,7 +72,10 @@ fn spawn_orc(
             }),
             ..default()
         },
-        Transform::from_translation(spawn_pos).with_scale(Vec3::splat(5.0)),
+        (
+            Transform::from_translation(spawn_pos).with_scale(Vec3::splat(5.0)),
+            YSort { offset: ORC_FOOT_OFFSET },
+        ),
         
         RigidBody::Dynamic,
         LockedAxes::ROTATION_LOCKED, // This prevents ALL rotation
@@ -64,9 +91,10 @@ fn spawn_orc(
         // Game logic components
         OrcEnemy::new(10.0, 1.0), // This now includes attack timers
         Health::new(10.0),
+        (orc_behaviour_tree(), OrcPerception::default(), OrcPath::default()),
         
         // Animation components
-        AnimationConfig::new(0, 7, 10), // Idle animation 
+        AnimationStateMachine::new(assets.animations.clone(), "Idle"),
         
         // Physics components for collision
         Collider::capsule(  
@@ -81,7 +109,22 @@ fn spawn_orc(
         ),
 
         Name::new(format!("Orc-{:?}", spawn_pos)),
+        LevelEntity, // Orcs stay behind when the player changes room
     )).id();
     
     info!("Spawned orc {:?} at {:?}", orc_entity, spawn_pos);
+    orc_entity
+}
+
+/// Spawn an orc as it was when the game was saved
+pub(crate) fn restore_orc(
+    commands: &mut Commands,
+    assets: &OrcAssets,
+    spawn_pos: Vec3,
+    health: f32,
+    enemy: OrcEnemy,
+) -> Entity {
+    let orc_entity = spawn_orc(commands, assets, spawn_pos);
+    commands.entity(orc_entity).insert((enemy, Health::new(health)));
+    orc_entity
 }
\ No newline at end of file
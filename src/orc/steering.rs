@@ -0,0 +1,99 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::*;
+
+// Separation between orcs
+const SEPARATION_RADIUS: f32 = 60.0;
+const SEPARATION_WEIGHT: f32 = 1.5;
+
+// Surround slots around the player
+pub const SURROUND_RADIUS: f32 = 70.0;
+const SEEK_WEIGHT: f32 = 1.0;
+
+// Wall avoidance feelers
+const FEELER_LENGTH: f32 = 80.0;
+const FEELER_ANGLE: f32 = std::f32::consts::FRAC_PI_6; // 30 degrees to each side
+const AVOIDANCE_WEIGHT: f32 = 2.0;
+
+/// Assign every chasing orc a spot on a circle around the player.
+/// Orcs keep their angular order so they spread out instead of crossing paths.
+pub fn surround_slots(player_pos: Vec2, chasers: &[(Entity, Vec2)]) -> HashMap<Entity, Vec2> {
+    let mut by_angle: Vec<(Entity, f32)> = chasers
+        .iter()
+        .map(|(entity, pos)| {
+            let offset = *pos - player_pos;
+            (*entity, offset.y.atan2(offset.x))
+        })
+        .collect();
+    by_angle.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut slots = HashMap::default();
+    let count = by_angle.len();
+    if count == 0 {
+        return slots;
+    }
+
+    // Rotate the whole ring so its average matches where the orcs already are
+    let step = std::f32::consts::TAU / count as f32;
+    let mean_offset = by_angle
+        .iter()
+        .enumerate()
+        .map(|(i, (_, angle))| Vec2::from_angle(*angle - i as f32 * step))
+        .sum::<Vec2>();
+    let base_angle = mean_offset.y.atan2(mean_offset.x);
+
+    for (i, (entity, _)) in by_angle.into_iter().enumerate() {
+        let slot_angle = base_angle + i as f32 * step;
+        slots.insert(entity, player_pos + Vec2::from_angle(slot_angle) * SURROUND_RADIUS);
+    }
+    slots
+}
+
+/// Push away from other orcs that are too close, stronger the closer they are
+pub fn separation(entity: Entity, pos: Vec2, neighbours: &[(Entity, Vec2)]) -> Vec2 {
+    let mut force = Vec2::ZERO;
+    for (other, other_pos) in neighbours {
+        if *other == entity {
+            continue;
+        }
+        let away = pos - *other_pos;
+        let dist = away.length();
+        if dist > 0.0 && dist < SEPARATION_RADIUS {
+            force += away / dist * (1.0 - dist / SEPARATION_RADIUS);
+        }
+    }
+    force
+}
+
+/// Cast three feelers ahead of the orc and steer away from any static wall they hit
+pub fn wall_avoidance(rapier_context: &RapierContext, pos: Vec2, heading: Vec2) -> Vec2 {
+    if heading == Vec2::ZERO {
+        return Vec2::ZERO;
+    }
+
+    // Only static, solid geometry counts as a wall
+    let filter = QueryFilter::only_fixed().exclude_sensors();
+    let mut force = Vec2::ZERO;
+
+    for angle in [0.0, FEELER_ANGLE, -FEELER_ANGLE] {
+        let feeler = Vec2::from_angle(angle).rotate(heading);
+        if let Some((_, toi)) = rapier_context.cast_ray(pos, feeler, FEELER_LENGTH, true, filter) {
+            // Closer hits push harder, directly away from the feeler direction
+            let urgency = 1.0 - toi / FEELER_LENGTH;
+            force -= feeler * urgency;
+
+            // Sidestep along the wall instead of just braking
+            let side = if angle >= 0.0 { -feeler.perp() } else { feeler.perp() };
+            force += side * urgency;
+        }
+    }
+    force
+}
+
+/// Blend the individual behaviours into a final velocity
+pub fn steer(seek: Vec2, separation: Vec2, avoidance: Vec2, speed: f32) -> Vec2 {
+    let desired = seek.normalize_or_zero() * SEEK_WEIGHT
+        + separation * SEPARATION_WEIGHT
+        + avoidance * AVOIDANCE_WEIGHT;
+    desired.normalize_or_zero() * speed
+}
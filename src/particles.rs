@@ -0,0 +1,339 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::animation::AnimationEvent;
+use crate::ysort::YSort;
+
+/// Animation event name of a foot touching the ground
+pub const FOOTSTEP_EVENT: &str = "footstep";
+
+/// What each particle looks like
+#[derive(Clone)]
+pub enum ParticleSprite {
+    /// Plain square, tinted by the particle color
+    Square,
+    /// Plays `first..=last` of a sprite sheet over the particle's life
+    Atlas {
+        image: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+        first: usize,
+        last: usize,
+    },
+}
+
+/// Emits particles in world space from wherever its entity is, so it can be
+/// attached to anything with a transform
+#[derive(Component, Clone)]
+pub struct ParticleEmitter {
+    /// Particles per second
+    pub rate: f32,
+    /// Particles spawned all at once on the first frame
+    pub burst: usize,
+    /// Seconds a particle lives
+    pub lifetime: f32,
+    pub speed: (f32, f32),
+    /// Main direction of the particles, in radians
+    pub direction: f32,
+    /// Random deviation around `direction`, TAU sends them everywhere
+    pub spread: f32,
+    /// Particles start anywhere within this radius of the emitter
+    pub radius: f32,
+    /// Constant acceleration, e.g. negative y for falling dust
+    pub gravity: Vec2,
+    pub color_start: Color,
+    pub color_end: Color,
+    pub size_start: f32,
+    pub size_end: f32,
+    pub sprite: ParticleSprite,
+    /// Drawn relative to the emitter, negative puts particles behind it
+    pub z_offset: f32,
+    /// Stop emitting after this many seconds, `None` emits forever
+    pub duration: Option<f32>,
+    /// Despawn the emitter entity once it stopped, for entities that only
+    /// exist to emit
+    pub despawn_when_done: bool,
+    elapsed: f32,
+    accumulator: f32,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            rate: 0.0,
+            burst: 0,
+            lifetime: 1.0,
+            speed: (20.0, 40.0),
+            direction: 0.0,
+            spread: TAU,
+            radius: 0.0,
+            gravity: Vec2::ZERO,
+            color_start: Color::WHITE,
+            color_end: Color::WHITE.with_alpha(0.0),
+            size_start: 4.0,
+            size_end: 1.0,
+            sprite: ParticleSprite::Square,
+            z_offset: 0.01,
+            duration: None,
+            despawn_when_done: false,
+            elapsed: 0.0,
+            accumulator: 0.0,
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// Embers left behind a fireball, using the fireball's own frames
+    pub fn fireball_trail(image: Handle<Image>, layout: Handle<TextureAtlasLayout>) -> Self {
+        Self {
+            rate: 40.0,
+            lifetime: 0.45,
+            speed: (5.0, 25.0),
+            radius: 6.0,
+            color_start: Color::srgba(1.0, 0.8, 0.5, 0.9),
+            color_end: Color::srgba(0.6, 0.1, 0.0, 0.0),
+            size_start: 14.0,
+            size_end: 4.0,
+            sprite: ParticleSprite::Atlas { image, layout, first: 6, last: 11 },
+            z_offset: -0.01, // Behind the fireball
+            ..default()
+        }
+    }
+
+    /// Burst where the player vanishes or reappears
+    pub fn blink_sparkles() -> Self {
+        Self {
+            burst: 30,
+            lifetime: 0.6,
+            speed: (60.0, 160.0),
+            radius: 20.0,
+            color_start: Color::srgba(0.85, 0.7, 1.0, 1.0),
+            color_end: Color::srgba(0.5, 0.2, 1.0, 0.0),
+            size_start: 5.0,
+            size_end: 1.0,
+            duration: Some(0.0),
+            despawn_when_done: true,
+            ..default()
+        }
+    }
+
+    /// Slow rising glints around the player while a shield comes up
+    pub fn shield_shimmer() -> Self {
+        Self {
+            rate: 60.0,
+            lifetime: 0.7,
+            speed: (15.0, 35.0),
+            direction: TAU / 4.0,
+            spread: 0.6,
+            radius: 35.0,
+            color_start: Color::srgba(0.5, 0.8, 1.0, 0.9),
+            color_end: Color::srgba(0.3, 0.5, 1.0, 0.0),
+            size_start: 4.0,
+            size_end: 2.0,
+            duration: Some(0.5),
+            despawn_when_done: true,
+            ..default()
+        }
+    }
+
+    /// Cloud kicked up by a falling orc
+    pub fn death_dust() -> Self {
+        Self {
+            burst: 24,
+            lifetime: 0.9,
+            speed: (30.0, 90.0),
+            direction: TAU / 4.0,
+            spread: 2.4,
+            radius: 25.0,
+            gravity: Vec2::new(0.0, -120.0),
+            color_start: Color::srgba(0.55, 0.5, 0.4, 0.8),
+            color_end: Color::srgba(0.4, 0.35, 0.3, 0.0),
+            size_start: 6.0,
+            size_end: 12.0,
+            duration: Some(0.0),
+            despawn_when_done: true,
+            ..default()
+        }
+    }
+
+    /// Small puff at the feet of a walking character
+    pub fn footstep_dust() -> Self {
+        Self {
+            burst: 4,
+            lifetime: 0.35,
+            speed: (10.0, 25.0),
+            direction: TAU / 4.0,
+            spread: 2.0,
+            radius: 4.0,
+            color_start: Color::srgba(0.6, 0.55, 0.45, 0.5),
+            color_end: Color::srgba(0.5, 0.45, 0.4, 0.0),
+            size_start: 3.0,
+            size_end: 6.0,
+            z_offset: -0.01, // Behind the character
+            duration: Some(0.0),
+            despawn_when_done: true,
+            ..default()
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.duration.is_some_and(|duration| self.elapsed >= duration)
+    }
+}
+
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    gravity: Vec2,
+    age: f32,
+    lifetime: f32,
+    color_start: Color,
+    color_end: Color,
+    size_start: f32,
+    size_end: f32,
+    frames: Option<(usize, usize)>,
+}
+
+// Particles are purely cosmetic, so they get their own generator instead of
+// drawing from GameRng and changing gameplay for a given seed
+#[derive(Resource)]
+struct ParticleRng(StdRng);
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ParticleRng(StdRng::from_entropy()))
+            .add_systems(Update, (update_particles, spawn_footstep_dust))
+            // Emitters need an up to date GlobalTransform, even on the frame they spawn
+            .add_systems(PostUpdate, emit_particles.after(TransformSystem::TransformPropagate));
+    }
+}
+
+/// Spawn a standalone emitter, typically a one-shot effect
+pub fn spawn_emitter(commands: &mut Commands, emitter: ParticleEmitter, position: Vec3) -> Entity {
+    commands
+        .spawn((emitter, Transform::from_translation(position), Name::new("ParticleEmitter")))
+        .id()
+}
+
+fn emit_particles(
+    mut commands: Commands,
+    mut emitter_query: Query<(Entity, &mut ParticleEmitter, &GlobalTransform)>,
+    mut rng: ResMut<ParticleRng>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut emitter, transform) in emitter_query.iter_mut() {
+        let mut count = std::mem::take(&mut emitter.burst);
+        if !emitter.finished() {
+            emitter.accumulator += emitter.rate * dt;
+            count += emitter.accumulator as usize;
+            emitter.accumulator = emitter.accumulator.fract();
+        }
+        emitter.elapsed += dt;
+
+        let origin = transform.translation();
+        for _ in 0..count {
+            spawn_particle(&mut commands, &emitter, origin, &mut rng.0);
+        }
+
+        // Particles live on their own, the emitter can go right away
+        if emitter.despawn_when_done && emitter.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn spawn_footstep_dust(
+    mut commands: Commands,
+    mut animation_events: EventReader<AnimationEvent>,
+    query: Query<(&Transform, Option<&YSort>)>,
+) {
+    for event in animation_events.read() {
+        if event.name != FOOTSTEP_EVENT {
+            continue;
+        }
+        let Ok((transform, y_sort)) = query.get(event.entity) else {
+            continue;
+        };
+        let feet = transform.translation + Vec3::Y * y_sort.map_or(0.0, |y_sort| y_sort.offset);
+        spawn_emitter(&mut commands, ParticleEmitter::footstep_dust(), feet);
+    }
+}
+
+fn spawn_particle(commands: &mut Commands, emitter: &ParticleEmitter, origin: Vec3, rng: &mut StdRng) {
+    let angle = emitter.direction + rng.gen_range(-0.5..=0.5) * emitter.spread;
+    let speed = rng.gen_range(emitter.speed.0..=emitter.speed.1);
+    let offset = Vec2::from_angle(rng.gen_range(0.0..TAU)) * rng.gen_range(0.0..=emitter.radius);
+    let position = origin + offset.extend(emitter.z_offset);
+
+    let (sprite, frames) = match &emitter.sprite {
+        ParticleSprite::Square => (
+            Sprite {
+                color: emitter.color_start,
+                custom_size: Some(Vec2::splat(emitter.size_start)),
+                ..default()
+            },
+            None,
+        ),
+        ParticleSprite::Atlas { image, layout, first, last } => (
+            Sprite {
+                image: image.clone(),
+                texture_atlas: Some(TextureAtlas { layout: layout.clone(), index: *first }),
+                color: emitter.color_start,
+                custom_size: Some(Vec2::splat(emitter.size_start)),
+                ..default()
+            },
+            Some((*first, *last)),
+        ),
+    };
+
+    commands.spawn((
+        sprite,
+        Transform::from_translation(position),
+        Particle {
+            velocity: Vec2::from_angle(angle) * speed,
+            gravity: emitter.gravity,
+            age: 0.0,
+            lifetime: emitter.lifetime,
+            color_start: emitter.color_start,
+            color_end: emitter.color_end,
+            size_start: emitter.size_start,
+            size_end: emitter.size_end,
+            frames,
+        },
+    ));
+}
+
+fn update_particles(
+    mut commands: Commands,
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut particle, mut transform, mut sprite) in particle_query.iter_mut() {
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let gravity = particle.gravity;
+        particle.velocity += gravity * dt;
+        transform.translation += (particle.velocity * dt).extend(0.0);
+
+        // Color, size and frame all follow the particle's life
+        let t = particle.age / particle.lifetime;
+        sprite.color = particle.color_start.mix(&particle.color_end, t);
+        sprite.custom_size = Some(Vec2::splat(particle.size_start.lerp(particle.size_end, t)));
+        if let (Some((first, last)), Some(atlas)) = (particle.frames, sprite.texture_atlas.as_mut()) {
+            atlas.index = first + ((last - first + 1) as f32 * t) as usize;
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::player_code::{Health, Player, Shield, PLAYER_MAX_HEALTH};
+
+const POTION_HEAL: f32 = 4.0;
+const CHARM_SHIELD: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PickupKind {
+    HealthPotion,
+    ShieldCharm,
+}
+
+impl PickupKind {
+    /// Parse the `kind` property used by level files
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "health_potion" => Some(PickupKind::HealthPotion),
+            "shield_charm" => Some(PickupKind::ShieldCharm),
+            _ => None,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            PickupKind::HealthPotion => Color::srgb(0.85, 0.15, 0.2),
+            PickupKind::ShieldCharm => Color::srgb(0.2, 0.45, 0.95),
+        }
+    }
+}
+
+/// Item lying on the floor, used up when the player touches it
+#[derive(Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+}
+
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, collect_pickups);
+    }
+}
+
+/// Spawn a pickup at a world position
+pub fn spawn_pickup(commands: &mut Commands, kind: PickupKind, position: Vec2) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color: kind.color(),
+                custom_size: Some(Vec2::splat(14.0)),
+                ..default()
+            },
+            Transform::from_translation(position.extend(-0.2)),
+            Pickup { kind },
+            Collider::ball(10.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Name::new(format!("Pickup-{:?}", kind)),
+        ))
+        .id()
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut player_query: Query<(Entity, &mut Health, &mut Shield), With<Player>>,
+    pickup_query: Query<&Pickup>,
+) {
+    let Ok((player, mut health, mut shield)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = *event else {
+            continue;
+        };
+        let other = if e1 == player {
+            e2
+        } else if e2 == player {
+            e1
+        } else {
+            continue;
+        };
+        let Ok(pickup) = pickup_query.get(other) else {
+            continue;
+        };
+
+        match pickup.kind {
+            PickupKind::HealthPotion => {
+                health.health = (health.health + POTION_HEAL).min(PLAYER_MAX_HEALTH);
+                info!("Picked up a health potion, health is now {:.1}", health.health);
+            }
+            PickupKind::ShieldCharm => {
+                // Same rule as the shield spell: never more shield than health
+                shield.shield = (shield.shield + CHARM_SHIELD).min(health.health);
+                info!("Picked up a shield charm, shield is now {:.1}", shield.shield);
+            }
+        }
+        commands.entity(other).despawn_recursive();
+    }
+}
@@ -1,4 +1,3 @@
-use std::process::exit;
 use bevy::prelude::*;
 use bevy::asset::Handle;
 use bevy::image::Image;
@@ -8,15 +7,29 @@ use bevy_rapier2d::prelude::Collider;
 use bevy_rapier2d::prelude::*;
 use std::time::Duration;
 
-use crate::animation::{AnimationConfig, SpriteState};
-use crate::player_animation::{FIRST_IDLE, FPS_IDLE, LAST_IDLE};
-use crate::player_movement::{FacingDirection, MovementState};
-use crate::orc::collision::AttackHitbox;
-use crate::ui_orc_counter::OrcDeathCounter;
+use crate::animation::{drive_animation_state_machines, AnimationLibrary, AnimationStateMachine, SpriteState};
+use crate::loading::{AppState, RequiredAssets};
+use crate::player_animation::{update_animation_state, update_sprite_direction};
+use crate::player_movement::{character_movement, FacingDirection, MovementState};
+use crate::camera::ScreenShakeEvent;
+use crate::shield::ShieldEvent;
+use crate::ysort::YSort;
 
 #[derive(Component)]
 pub struct Player;
 
+/// Health the player starts with, healing never goes above it
+pub const PLAYER_MAX_HEALTH: f32 = 20.0;
+
+/// Distance from the player's center down to their feet, for y-sorting
+pub const PLAYER_FOOT_OFFSET: f32 = -50.0;
+
+/// Seconds the player can't be hurt again after taking a hit
+pub const INVULNERABILITY_DURATION: f32 = 1.5;
+
+/// Camera trauma added every time the player takes damage
+const DAMAGE_TRAUMA: f32 = 0.5;
+
 /// Health component for entities
 #[derive(Component)]
 pub struct PlayerHealthPlugin;
@@ -36,10 +49,24 @@ impl Plugin for PlayerHealthPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_event::<PlayerDamageEvent>()
+            .add_event::<ShieldEvent>()
             .add_systems(Update, (
-                check_orc_attack_collisions,
                 handle_player_damage,
                 handle_invulnerability,
+            ).run_if(in_state(AppState::InGame)));
+    }
+}
+
+/// Spawns the player, and moves and animates them from the keyboard
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_player)
+            .add_systems(Update, (
+                character_movement,
+                update_sprite_direction,
+                update_animation_state.before(drive_animation_state_machines),
             ));
     }
 }
@@ -70,15 +97,28 @@ impl Shield {
         }
     }
 }
+fn spawn_player(
+    commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    // Create the texture atlas for character sprite
+    // Layout: 16x32 sprites, 9 columns, 10 rows
+    let texture = required.image(&asset_server, "characters_atlas.png", UVec2::new(144, 320));
+    let layout = TextureAtlasLayout::from_grid(UVec2::new(16, 32), 9, 10, None, None);
+    let texture_atlas_layout = atlas_layouts.add(layout);
+    let animations = required.load(&asset_server, "animations/player.anim.ron");
+    setup_player(commands, texture, texture_atlas_layout, animations);
+}
+
 // Set up the player entity with all necessary components
 pub fn setup_player(
     mut commands: Commands,
     texture: Handle<Image>,
     texture_atlas_layout: Handle<TextureAtlasLayout>,
+    animations: Handle<AnimationLibrary>,
 ) {
-    // Create the initial idle animation configuration
-    let idle_animation_config = AnimationConfig::new(FIRST_IDLE, LAST_IDLE, FPS_IDLE);
-
     // Spawn the player with all required components
     let player_entity = commands.spawn((
         RigidBody::Dynamic,
@@ -96,74 +136,46 @@ pub fn setup_player(
             image: texture,
             texture_atlas: Some(TextureAtlas {
                 layout: texture_atlas_layout,
-                index: FIRST_IDLE,
+                index: 0, // Replaced by the idle clip once the animations loaded
             }),
             ..default()
         },
         Transform::from_scale(Vec3::splat(5.0)),
+        YSort { offset: PLAYER_FOOT_OFFSET },
 
         // Game logic components
         Player,
-        Health::new(20.0),
+        Health::new(PLAYER_MAX_HEALTH),
         Shield::new(0.0),
         FacingDirection {facing_right: true},
         MovementState::Idle,
         SpriteState::Idle,
-        idle_animation_config,
+        AnimationStateMachine::new(animations, "Idle"),
     )).id();
 }
 
-fn check_orc_attack_collisions(
-    mut collision_events: EventReader<CollisionEvent>,
-    player_query: Query<Entity, With<Player>>,
-    attack_hitbox_query: Query<&AttackHitbox>,
-    mut damage_events: EventWriter<PlayerDamageEvent>,
-) {
-    // Get the player entity
-    if let Ok(player_entity) = player_query.get_single() {
-        for event in collision_events.read() {
-            if let CollisionEvent::Started(e1, e2, _) = event {
-                // Check if this collision involves the player and an attack hitbox
-                let (player_collided, hitbox_entity) = if *e1 == player_entity {
-                    (true, *e2)
-                } else if *e2 == player_entity {
-                    (true, *e1)
-                } else {
-                    (false, *e1) // Not relevant
-                };
-
-                // If the player is involved in the collision
-                if player_collided {
-                    // Check if the other entity is an attack hitbox
-                    if let Ok(_attack_hitbox) = attack_hitbox_query.get(hitbox_entity) {
-                        // Send damage event - 1.0 damage per heart
-                        damage_events.send(PlayerDamageEvent { damage: 1.0 });
-                        info!("Player hit by orc attack!");
-                    }
-                }
-            }
-        }
-    }
-}
-
 // System to handle damage to the player
 fn handle_player_damage(
     mut commands: Commands,
     mut damage_events: EventReader<PlayerDamageEvent>,
     mut player_query: Query<(Entity, &mut Health, &mut Shield), (With<Player>, Without<Invulnerable>)>,
-    kill_count: Res<OrcDeathCounter>,
+    mut shake_events: EventWriter<ScreenShakeEvent>,
+    mut shield_events: EventWriter<ShieldEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
     // Only process if player exists and isn't invulnerable
     if let Ok((player_entity, mut health, mut shield)) = player_query.get_single_mut() {
         for event in damage_events.read() {
             let damage_amount = event.damage;
+            shake_events.send(ScreenShakeEvent { trauma: DAMAGE_TRAUMA });
             
             // Try to absorb damage with shield first
             if shield.shield > 0.0 {
                 // Shield can absorb some or all damage
                 let shield_absorption = shield.shield.min(damage_amount);
                 shield.shield -= shield_absorption;
-                
+                shield_events.send(ShieldEvent::Depleted);
+
                 let remaining_damage = damage_amount - shield_absorption;
                 
                 if remaining_damage > 0.0 {
@@ -179,13 +191,14 @@ fn handle_player_damage(
             }
 
             if health.health <= 0.0 {
-                println!("Your dead!\nOrcs killed: {}", kill_count.count);
-                exit(0);
+                println!("Your dead!");
+                next_state.set(AppState::GameOver);
+                return;
             }
 
             // Add invulnerability period
             commands.entity(player_entity).insert(Invulnerable {
-                timer: Timer::new(Duration::from_secs_f32(1.5), TimerMode::Once),
+                timer: Timer::new(Duration::from_secs_f32(INVULNERABILITY_DURATION), TimerMode::Once),
             });
         }
     }
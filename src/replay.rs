@@ -0,0 +1,189 @@
+use std::fs;
+use std::io;
+use std::time::{Duration, Instant};
+
+use bevy::input::keyboard::{KeyboardFocusLost, KeyboardInput};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::PrimaryWindow;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::loading::AppState;
+use crate::rng::GameRng;
+
+/// Launch option to record the keyboard: `--record <file>`, written on exit
+const RECORD_ARG: &str = "--record";
+/// Launch option to play a recording back instead of the keyboard: `--replay <file>`
+const REPLAY_ARG: &str = "--replay";
+
+/// Length of every frame of a recorded session, so a replay runs the exact same simulation
+const RECORDING_TIMESTEP: f32 = 1.0 / 60.0;
+
+/// Everything a session needs to play out the same way again
+#[derive(Serialize, Deserialize)]
+struct InputRecording {
+    seed: u64,
+    timestep: f32,
+    /// Only the frames with input, in order
+    frames: Vec<RecordedFrame>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedFrame {
+    /// Counted from the end of the loading screen, which takes a different
+    /// number of frames on every launch
+    frame: u32,
+    keys: Vec<KeyboardInput>,
+}
+
+#[derive(Resource)]
+struct Recorder {
+    path: String,
+    recording: InputRecording,
+    frame: u32,
+}
+
+#[derive(Resource)]
+struct Replayer {
+    recording: InputRecording,
+    next: usize,
+    frame: u32,
+}
+
+/// Holds every frame to at least one timestep of wall clock time. Without it
+/// a fast display would play the fixed steps too quickly, 144 of them a second
+/// at 144 Hz. A frame slower than the step still slows the game down.
+#[derive(Resource)]
+struct FramePacer {
+    step: Duration,
+    frame_start: Instant,
+}
+
+impl FramePacer {
+    fn new(step: f32) -> Self {
+        Self { step: Duration::from_secs_f32(step), frame_start: Instant::now() }
+    }
+}
+
+impl Replayer {
+    fn finished(&self) -> bool {
+        self.next >= self.recording.frames.len()
+    }
+}
+
+/// Records or replays the keyboard, depending on the launch options.
+/// Needs to be added after `RngPlugin`, it records or overrides the seed.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = path_from_args(REPLAY_ARG) {
+            let recording = match read_recording(&path) {
+                Ok(recording) => recording,
+                Err(err) => {
+                    error!("Could not read recording {}: {}", path, err);
+                    return;
+                }
+            };
+            info!("Replaying {} (seed {})", path, recording.seed);
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(recording.timestep)))
+                .insert_resource(FramePacer::new(recording.timestep))
+                .insert_resource(GameRng::from_seed(recording.seed))
+                .insert_resource(Replayer { recording, next: 0, frame: 0 })
+                .add_systems(
+                    PreUpdate,
+                    replay_input
+                        .before(InputSystem)
+                        .run_if(not(in_state(AppState::Loading))),
+                )
+                .add_systems(Last, pace_frames);
+        } else if let Some(path) = path_from_args(RECORD_ARG) {
+            let seed = app.world().resource::<GameRng>().seed;
+            info!("Recording input to {}", path);
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(RECORDING_TIMESTEP)))
+                .insert_resource(FramePacer::new(RECORDING_TIMESTEP))
+                .insert_resource(Recorder {
+                    path,
+                    recording: InputRecording { seed, timestep: RECORDING_TIMESTEP, frames: Vec::new() },
+                    frame: 0,
+                })
+                .add_systems(PreUpdate, record_input.run_if(not(in_state(AppState::Loading))))
+                .add_systems(Last, (write_recording_on_exit, pace_frames));
+        }
+    }
+}
+
+fn path_from_args(option: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != option).nth(1)
+}
+
+fn read_recording(path: &str) -> io::Result<InputRecording> {
+    let text = fs::read_to_string(path)?;
+    ron::from_str(&text).map_err(io::Error::other)
+}
+
+fn record_input(mut recorder: ResMut<Recorder>, mut key_events: EventReader<KeyboardInput>) {
+    let keys: Vec<KeyboardInput> = key_events.read().cloned().collect();
+    let frame = recorder.frame;
+    if !keys.is_empty() {
+        recorder.recording.frames.push(RecordedFrame { frame, keys });
+    }
+    recorder.frame += 1;
+}
+
+fn write_recording_on_exit(recorder: Res<Recorder>, mut exit_events: EventReader<AppExit>) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+    let written = ron::ser::to_string_pretty(&recorder.recording, PrettyConfig::default())
+        .map_err(io::Error::other)
+        .and_then(|text| fs::write(&recorder.path, text));
+    match written {
+        Ok(()) => info!("Input recorded to {}", recorder.path),
+        Err(err) => error!("Could not write recording {}: {}", recorder.path, err),
+    }
+}
+
+// Every frame advances time by exactly one step, so it has to last one step too
+fn pace_frames(mut pacer: ResMut<FramePacer>) {
+    let elapsed = pacer.frame_start.elapsed();
+    if elapsed < pacer.step {
+        std::thread::sleep(pacer.step - elapsed);
+    }
+    pacer.frame_start = Instant::now();
+}
+
+// Feed the recorded keys in before bevy turns them into ButtonInput, so
+// both the key events and the pressed keys match the recorded session
+fn replay_input(
+    mut replayer: ResMut<Replayer>,
+    mut key_events: ResMut<Events<KeyboardInput>>,
+    mut focus_events: ResMut<Events<KeyboardFocusLost>>,
+    window_query: Query<Entity, With<PrimaryWindow>>,
+) {
+    if replayer.finished() {
+        return; // The keyboard is back to the player
+    }
+
+    // The real keyboard is ignored while the replay runs, losing focus would release every key
+    key_events.clear();
+    focus_events.clear();
+
+    let window = window_query.get_single().unwrap_or(Entity::PLACEHOLDER);
+    let replayer = &mut *replayer;
+    if let Some(recorded) = replayer.recording.frames.get(replayer.next) {
+        if recorded.frame == replayer.frame {
+            for key in recorded.keys.iter() {
+                key_events.send(KeyboardInput { window, ..key.clone() });
+            }
+            replayer.next += 1;
+        }
+    }
+    replayer.frame += 1;
+
+    if replayer.finished() {
+        info!("Replay finished after {} frames", replayer.frame);
+    }
+}
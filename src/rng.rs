@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Shared random number generator for gameplay code.
+/// Everything random should draw from here so a run can be reproduced from its seed.
+#[derive(Resource)]
+pub struct GameRng {
+    pub seed: u64,
+    pub rng: StdRng,
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+/// Launch option to replay a run: `--seed <number>`
+const SEED_ARG: &str = "--seed";
+
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = seed_from_args().unwrap_or_else(rand::random::<u64>);
+        info!("Game RNG seed: {}", seed);
+        app.insert_resource(GameRng::from_seed(seed));
+    }
+}
+
+fn seed_from_args() -> Option<u64> {
+    let mut args = std::env::args().skip_while(|arg| arg != SEED_ARG).skip(1);
+    let value = args.next()?;
+    match value.parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            warn!("Ignoring invalid seed '{}'", value);
+            None
+        }
+    }
+}
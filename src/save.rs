@@ -0,0 +1,332 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::camera::SmoothCamera;
+use crate::leaderboard::RunStats;
+use crate::level::{CurrentLevel, LevelEntity, LevelSource};
+use crate::orc::boss::{restore_boss, Boss};
+use crate::orc::{restore_orc, OrcAssets, OrcEnemy, OrcState};
+use crate::pickup::{spawn_pickup, Pickup, PickupKind};
+use crate::player_code::{Health, Invulnerable, Player, Shield, PLAYER_MAX_HEALTH};
+use crate::rng::GameRng;
+use crate::spell::{SpellCastEvent, SpellType, UnlockedSpells};
+use crate::storage;
+use crate::ui_orc_counter::OrcDeathCounter;
+
+/// Number of save slots offered on the main menu, numbered from 1
+pub const SAVE_SLOTS: usize = 3;
+
+/// Everything needed to resume a run
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub level: SavedLevel,
+    /// Seed of the run, the next dungeon floors are derived from it
+    pub seed: u64,
+    pub player_position: [f32; 2],
+    pub health: f32,
+    pub shield: f32,
+    pub max_shield: f32,
+    pub unlocked_spells: Vec<SpellType>,
+    pub orcs_killed: u32,
+    /// Orcs that were still alive, the level's spawn points are ignored on load
+    pub orcs: Vec<SavedOrc>,
+    /// Pickups not collected yet, the level's own are ignored on load
+    #[serde(default)]
+    pub pickups: Vec<SavedPickup>,
+    #[serde(default)]
+    pub stats: RunStats,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum SavedLevel {
+    /// Asset path of a Tiled map
+    Map(String),
+    Dungeon { seed: u64, floor: u32 },
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedOrc {
+    pub position: [f32; 2],
+    pub health: f32,
+    pub enemy: OrcEnemy,
+    pub boss: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedPickup {
+    pub position: [f32; 2],
+    pub kind: PickupKind,
+}
+
+impl SaveGame {
+    /// One line describing the run, shown next to its slot
+    pub fn summary(&self) -> String {
+        let level = match &self.level {
+            SavedLevel::Map(path) => Path::new(path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone()),
+            SavedLevel::Dungeon { floor, .. } => format!("Dungeon floor {}", floor + 1),
+        };
+        format!("{} - {:.0} health - {} orcs killed", level, self.health, self.orcs_killed)
+    }
+}
+
+/// Slot the "save" spell writes to, picked on the main menu
+#[derive(Resource)]
+pub struct ActiveSlot(pub usize);
+
+impl Default for ActiveSlot {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Save being loaded. Its level is requested right away, the player, the
+/// orcs and the pickups are restored once the level has spawned.
+#[derive(Resource)]
+pub struct PendingLoad(pub SaveGame);
+
+/// Start a fresh run on the first level, replacing whatever was played before
+#[derive(Event)]
+pub struct NewGameEvent;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveSlot>()
+            .add_event::<NewGameEvent>()
+            .add_systems(Update, (save_on_spell, start_new_game))
+            .add_systems(
+                Update,
+                (
+                    start_loading_save.run_if(resource_added::<PendingLoad>),
+                    finish_loading_save.run_if(resource_exists::<PendingLoad>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn slot_path(slot: usize) -> PathBuf {
+    storage::data_dir().join("saves").join(format!("slot{}.ron", slot))
+}
+
+/// The save in `slot`, `None` if the slot is empty or unreadable
+pub fn read_save(slot: usize) -> Option<SaveGame> {
+    let path = slot_path(slot);
+    let text = fs::read_to_string(&path).ok()?;
+    match ron::from_str(&text) {
+        Ok(save) => Some(save),
+        Err(err) => {
+            error!("Could not read save {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+fn write_save(slot: usize, save: &SaveGame) -> io::Result<()> {
+    let path = slot_path(slot);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text = ron::ser::to_string_pretty(save, PrettyConfig::default()).map_err(io::Error::other)?;
+    fs::write(path, text)
+}
+
+fn save_on_spell(
+    mut spell_events: EventReader<SpellCastEvent>,
+    player_query: Query<(&Transform, &Health, &Shield), With<Player>>,
+    orc_query: Query<(&Transform, &Health, &OrcEnemy, Has<Boss>)>,
+    pickup_query: Query<(&Transform, &Pickup)>,
+    current_level: Res<CurrentLevel>,
+    rng: Res<GameRng>,
+    unlocked: Res<UnlockedSpells>,
+    kill_count: Res<OrcDeathCounter>,
+    stats: Res<RunStats>,
+    slot: Res<ActiveSlot>,
+) {
+    for event in spell_events.read() {
+        if event.spell_type != SpellType::Save {
+            continue;
+        }
+        let Ok((player_transform, health, shield)) = player_query.get_single() else {
+            continue;
+        };
+        if !current_level.spawned {
+            warn!("Can't save while the level is loading");
+            continue;
+        }
+
+        let level = match &current_level.source {
+            LevelSource::Map(handle) => {
+                let Some(path) = handle.path() else {
+                    warn!("Current map has no asset path, not saving");
+                    continue;
+                };
+                SavedLevel::Map(path.to_string())
+            }
+            LevelSource::Dungeon { seed, floor } => SavedLevel::Dungeon { seed: *seed, floor: *floor },
+            // Only scripted scenarios play there, and they don't save
+            LevelSource::Arena => continue,
+        };
+        let orcs = orc_query
+            .iter()
+            .filter(|(_, _, orc, _)| orc.state != OrcState::Dying)
+            .map(|(transform, health, orc, boss)| SavedOrc {
+                position: transform.translation.truncate().to_array(),
+                health: health.health,
+                enemy: orc.clone(),
+                boss,
+            })
+            .collect();
+        let pickups = pickup_query
+            .iter()
+            .map(|(transform, pickup)| SavedPickup {
+                position: transform.translation.truncate().to_array(),
+                kind: pickup.kind,
+            })
+            .collect();
+
+        let save = SaveGame {
+            level,
+            seed: rng.seed,
+            player_position: player_transform.translation.truncate().to_array(),
+            health: health.health,
+            shield: shield.shield,
+            max_shield: shield.max_shield,
+            unlocked_spells: unlocked.spells.clone(),
+            orcs_killed: kill_count.count,
+            orcs,
+            pickups,
+            stats: stats.clone(),
+        };
+        match write_save(slot.0, &save) {
+            Ok(()) => info!("Game saved to slot {}", slot.0),
+            Err(err) => error!("Could not save to slot {}: {}", slot.0, err),
+        }
+    }
+}
+
+// Tear down the current level and ask for the saved one
+fn start_loading_save(
+    mut commands: Commands,
+    pending: Res<PendingLoad>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut rng: ResMut<GameRng>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    asset_server: Res<AssetServer>,
+) {
+    let save = &pending.0;
+    for entity in level_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let source = match &save.level {
+        SavedLevel::Map(path) => LevelSource::Map(asset_server.load(path.clone())),
+        SavedLevel::Dungeon { seed, floor } => LevelSource::Dungeon { seed: *seed, floor: *floor },
+    };
+    *current_level = CurrentLevel {
+        source,
+        spawn_point: None,
+        spawned: false,
+        from_save: true,
+    };
+    *rng = GameRng::from_seed(save.seed);
+}
+
+// Put the player, the orcs and the pickups back once the level is there
+fn finish_loading_save(
+    mut commands: Commands,
+    pending: Res<PendingLoad>,
+    current_level: Res<CurrentLevel>,
+    mut player_query: Query<(&mut Transform, &mut Health, &mut Shield), With<Player>>,
+    mut camera_query: Query<&mut SmoothCamera>,
+    mut unlocked: ResMut<UnlockedSpells>,
+    mut kill_count: ResMut<OrcDeathCounter>,
+    mut stats: ResMut<RunStats>,
+    orc_assets: Res<OrcAssets>,
+) {
+    if !current_level.spawned {
+        return;
+    }
+    let save = &pending.0;
+
+    let player_position = Vec2::from_array(save.player_position);
+    if let Ok((mut transform, mut health, mut shield)) = player_query.get_single_mut() {
+        transform.translation.x = player_position.x;
+        transform.translation.y = player_position.y;
+        health.health = save.health;
+        shield.shield = save.shield;
+        shield.max_shield = save.max_shield;
+    }
+    if let Ok(mut camera) = camera_query.get_single_mut() {
+        camera.focus = player_position;
+    }
+
+    unlocked.spells = save.unlocked_spells.clone();
+    kill_count.count = save.orcs_killed;
+    *stats = save.stats.clone();
+
+    for orc in save.orcs.iter() {
+        let position = Vec2::from_array(orc.position).extend(0.0);
+        if orc.boss {
+            restore_boss(&mut commands, &orc_assets, position, orc.health, orc.enemy.clone());
+        } else {
+            restore_orc(&mut commands, &orc_assets, position, orc.health, orc.enemy.clone());
+        }
+    }
+
+    for pickup in save.pickups.iter() {
+        let entity = spawn_pickup(&mut commands, pickup.kind, Vec2::from_array(pickup.position));
+        commands.entity(entity).insert(LevelEntity);
+    }
+
+    info!("Save loaded ({} orcs, {} pickups)", save.orcs.len(), save.pickups.len());
+    commands.remove_resource::<PendingLoad>();
+}
+
+// Back to the first level with a healthy player and nothing achieved yet
+fn start_new_game(
+    mut commands: Commands,
+    mut new_game_events: EventReader<NewGameEvent>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_entities: Query<Entity, With<LevelEntity>>,
+    mut player_query: Query<(Entity, &mut Health, &mut Shield, &mut Sprite), With<Player>>,
+    mut unlocked: ResMut<UnlockedSpells>,
+    mut kill_count: ResMut<OrcDeathCounter>,
+    mut stats: ResMut<RunStats>,
+    asset_server: Res<AssetServer>,
+    rng: Res<GameRng>,
+) {
+    if new_game_events.read().count() == 0 {
+        return;
+    }
+
+    for entity in level_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    *current_level = CurrentLevel {
+        source: LevelSource::start(&asset_server, &rng),
+        spawn_point: None,
+        spawned: false,
+        from_save: false,
+    };
+
+    if let Ok((player, mut health, mut shield, mut sprite)) = player_query.get_single_mut() {
+        health.health = PLAYER_MAX_HEALTH;
+        *shield = Shield::new(0.0);
+        sprite.color.set_alpha(1.0);
+        commands.entity(player).remove::<Invulnerable>();
+    }
+
+    *unlocked = UnlockedSpells::default();
+    kill_count.count = 0;
+    *stats = RunStats::default();
+}
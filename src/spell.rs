@@ -4,11 +4,14 @@ use bevy::{
         Key,
         KeyboardInput},
 };
-use std::process::exit; // For the "exit" spell
+use serde::{Deserialize, Serialize};
 use crate::player_code::Player;
+use crate::loading::{AppState, RequiredAssets};
 
 // Constants for display positioning
 const SPELL_TEXT_OFFSET_Y: f32 = 40.0; // Offset from player
+// Above the foreground layers (100) and below the lighting overlay (900)
+const SPELL_TEXT_Z: f32 = 200.0;
 
 // Stack resource to hold spell characters
 #[derive(Resource, Default)]
@@ -54,16 +57,44 @@ pub struct SpellTextBackground;
 
 // Enum for spell types
 #[derive(Debug, Clone, Copy)]
-#[derive(PartialEq)]
+#[derive(PartialEq, Serialize, Deserialize)]
 pub enum SpellType {
     Fireball,
     Blink,
     Shield,
     Exit,
     Spellbook,
+    Save,
     Unknown,
 }
 
+/// Spells the player knows. Typing any other spell fizzles like a misspelling.
+#[derive(Resource)]
+pub struct UnlockedSpells {
+    pub spells: Vec<SpellType>,
+}
+
+impl Default for UnlockedSpells {
+    fn default() -> Self {
+        Self {
+            spells: vec![
+                SpellType::Fireball,
+                SpellType::Blink,
+                SpellType::Shield,
+                SpellType::Exit,
+                SpellType::Spellbook,
+                SpellType::Save,
+            ],
+        }
+    }
+}
+
+impl UnlockedSpells {
+    pub fn contains(&self, spell_type: SpellType) -> bool {
+        self.spells.contains(&spell_type)
+    }
+}
+
 // Event for when a spell is cast
 #[derive(Event)]
 pub struct SpellCastEvent {
@@ -77,10 +108,11 @@ pub struct StackSpellSystemPlugin;
 impl Plugin for StackSpellSystemPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SpellStack>()
+            .init_resource::<UnlockedSpells>()
             .add_event::<SpellCastEvent>()
             .add_systems(Startup, setup_spell_system)
             .add_systems(Update, (
-                handle_spell_input,
+                handle_spell_input.run_if(in_state(AppState::InGame)),
                 update_spell_text,
                 update_text_position,
                 execute_spells,
@@ -91,7 +123,8 @@ impl Plugin for StackSpellSystemPlugin {
 // Set up the visual elements for the spell system
 pub fn setup_spell_system(
     mut commands: Commands,
-    asset_server: Res<AssetServer>
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
 ) {
     // Background for the spell text
     commands.spawn((
@@ -100,7 +133,7 @@ pub fn setup_spell_system(
             custom_size: Some(Vec2::new(200.0, 35.0)), //todo: resize with text
             ..default()
         },
-        Transform::from_xyz(0.0, SPELL_TEXT_OFFSET_Y, 1.0),
+        Transform::from_xyz(0.0, SPELL_TEXT_OFFSET_Y, SPELL_TEXT_Z),
         Visibility::Hidden,
         SpellTextBackground,
     ));
@@ -110,12 +143,12 @@ pub fn setup_spell_system(
         // Empty text that will be filled when typing
         Text2d::new(""),
         TextFont {
-            font: asset_server.load("fonts/NicoClean-Monospaced.ttf"),
+            font: required.font(&asset_server, "fonts/NicoClean-Monospaced.ttf"),
             font_size: 20.0,
             ..default()
         },
         // Positioning will be handled in update_text_position system
-        Transform::from_xyz(0.0, SPELL_TEXT_OFFSET_Y, 2.0),
+        Transform::from_xyz(0.0, SPELL_TEXT_OFFSET_Y, SPELL_TEXT_Z + 1.0),
         GlobalTransform::default(),
         Visibility::Hidden,
         InheritedVisibility::default(),
@@ -128,6 +161,7 @@ pub fn setup_spell_system(
 // Handle keyboard input for spell casting
 pub fn handle_spell_input(
     mut spell_stack: ResMut<SpellStack>,
+    unlocked: Res<UnlockedSpells>,
     mut spell_cast_events: EventWriter<SpellCastEvent>,
     mut key_events: EventReader<KeyboardInput>,
     kbd: Res<ButtonInput<KeyCode>>,
@@ -160,7 +194,10 @@ pub fn handle_spell_input(
     if kbd.just_pressed(KeyCode::Enter) {
         let spell_name = spell_stack.as_string();
         if !spell_name.is_empty() {
-            let spell_type = identify_spell(&spell_name);
+            let spell_type = match identify_spell(&spell_name) {
+                known if unlocked.contains(known) => known,
+                _ => SpellType::Unknown,
+            };
 
             // Emit spell cast event
             spell_cast_events.send(SpellCastEvent {
@@ -243,20 +280,22 @@ pub fn update_text_position(
     }
 }
 
-// Identify what spell is being cast based on input
-fn identify_spell(input: &str) -> SpellType {
+/// The spell a typed name casts, case-insensitive. Anything else is `Unknown`.
+pub fn identify_spell(input: &str) -> SpellType {
     match input.to_lowercase().as_str() {
         "fireball" => SpellType::Fireball,
         "blink" => SpellType::Blink,
         "shield" => SpellType::Shield,
         "exit" => SpellType::Exit,
         "spellbook" => SpellType::Spellbook,
+        "save" => SpellType::Save,
         _ => SpellType::Unknown,
     }
 }
 
 pub fn execute_spells(
     mut spell_events: EventReader<SpellCastEvent>,
+    mut exit_events: EventWriter<AppExit>,
 ) {
     for event in spell_events.read() {
         match event.spell_type {
@@ -272,11 +311,16 @@ pub fn execute_spells(
             },
             SpellType::Exit => {
                 println!("Casting Exit");
-                exit(0);
+                // Through AppExit so the app can still write files on the way out
+                exit_events.send(AppExit::Success);
             },
             SpellType::Spellbook => {
                 println!("Casting Spellbook spell!");
             }
+            SpellType::Save => {
+                // Writing the save slot is handled in save_on_spell
+                info!("Casting Save spell!");
+            }
             SpellType::Unknown => {
                 println!("Unknown spell: {}", event.spell_name);
             },
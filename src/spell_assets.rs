@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use crate::loading::RequiredAssets;
+
+/// Textures and atlas layouts shared by every cast, loaded once at startup
+#[derive(Resource)]
+pub struct SpellAssets {
+    pub fireball_texture: Handle<Image>,
+    pub fireball_layout: Handle<TextureAtlasLayout>,
+    pub blink_texture: Handle<Image>,
+    pub blink_layout: Handle<TextureAtlasLayout>,
+    pub spellbook_texture: Handle<Image>,
+}
+
+pub struct SpellAssetPlugin;
+impl Plugin for SpellAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_spell_assets);
+    }
+}
+
+fn load_spell_assets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    // Both spell sheets are made of 32x32 frames
+    let fireball_texture = required.image(&asset_server, "spells/03.png", UVec2::new(192, 64));
+    let fireball_layout = layouts.add(TextureAtlasLayout::from_grid(UVec2::new(32, 32), 6, 2, None, None));
+    let blink_texture = required.image(&asset_server, "spells/10.png", UVec2::new(192, 64));
+    let blink_layout = layouts.add(TextureAtlasLayout::from_grid(UVec2::new(32, 32), 3, 2, None, None));
+    let spellbook_texture = required.image(&asset_server, "UI/Spellbook.png", UVec2::new(512, 320));
+
+    commands.insert_resource(SpellAssets {
+        fireball_texture,
+        fireball_layout,
+        blink_texture,
+        blink_layout,
+        spellbook_texture,
+    });
+}
@@ -1,15 +1,15 @@
 use bevy::{
     prelude::*,
     time::Time,
-    time::Real,
 };
 use crate::spell::{SpellCastEvent, SpellType};
 use crate::player_code::Player;
+use crate::spell_assets::SpellAssets;
 
 // Constants for the spellbook display
 const SPELLBOOK_DISPLAY_TIME: f32 = 7.5; // How long the spellbook stays visible
 const SPELLBOOK_OFFSET_Y: f32 = 80.0; // Offset from player position
-const SPELLBOOK_Z_LAYER: f32 = 10.0; // Make sure spellbook displays above other elements
+const SPELLBOOK_Z: f32 = 210.0; // Above the foreground layers and the spell prompt, below the lighting overlay
 
 // Plugin for managing the spellbook display
 pub struct SpellbookPlugin;
@@ -53,7 +53,7 @@ fn handle_spellbook_events(
     mut spell_events: EventReader<SpellCastEvent>,
     mut spellbook_state: ResMut<SpellbookState>,
     player_query: Query<&Transform, With<Player>>,
-    asset_server: Res<AssetServer>,
+    spell_assets: Res<SpellAssets>,
 ) {
     for event in spell_events.read() {
         if event.spell_type == SpellType::Spellbook {
@@ -64,17 +64,17 @@ fn handle_spellbook_events(
 
             // Get player position for spawning the spellbook
             if let Ok(player_transform) = player_query.get_single() {
-                // Load spellbook texture
-                let spellbook_texture = asset_server.load("UI/Spellbook.png");
-
                 // Calculate spawn position above player
-                let spawn_position = player_transform.translation +
-                    Vec3::new(0.0, SPELLBOOK_OFFSET_Y, SPELLBOOK_Z_LAYER);
+                let spawn_position = Vec3::new(
+                    player_transform.translation.x,
+                    player_transform.translation.y + SPELLBOOK_OFFSET_Y,
+                    SPELLBOOK_Z,
+                );
 
                 // Spawn the spellbook entity
                 let spellbook_entity = commands.spawn((
                     Sprite {
-                        image: spellbook_texture,
+                        image: spell_assets.spellbook_texture.clone(),
                         ..default()
                     },
                     Transform::from_translation(spawn_position)
@@ -98,7 +98,7 @@ fn handle_spellbook_events(
 // Update the spellbook display based on timer
 fn update_spellbook_display(
     mut commands: Commands,
-    time: Res<Time<Real>>,
+    time: Res<Time>,
     mut spellbook_state: ResMut<SpellbookState>,
 ) {
     // If the spellbook is visible, update the timer
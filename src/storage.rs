@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+/// Folder name used inside the platform's user data directory
+const APP_DIR: &str = "misspelled";
+
+/// Where the game keeps files that outlive a run, e.g. save slots.
+/// `$XDG_DATA_HOME` or `~/.local/share` on Linux, `%APPDATA%` on Windows,
+/// and the working directory when none of those are set.
+pub fn data_dir() -> PathBuf {
+    let env_dir = |name: &str| std::env::var_os(name).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    let base = env_dir("XDG_DATA_HOME")
+        .or_else(|| env_dir("HOME").map(|home| home.join(".local").join("share")))
+        .or_else(|| env_dir("APPDATA"))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join(APP_DIR)
+}
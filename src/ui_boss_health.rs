@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use crate::loading::RequiredAssets;
+use crate::orc::{OrcEnemy, OrcState};
+use crate::orc::boss::{Boss, BossPhase};
+use crate::player_code::Health;
+
+const BOSS_BAR_WIDTH: f32 = 600.0;
+const BOSS_BAR_HEIGHT: f32 = 24.0;
+
+pub struct BossHealthBarPlugin;
+
+// Components for the boss health bar UI elements
+#[derive(Component)]
+struct BossHealthBar;
+
+#[derive(Component)]
+struct BossHealthBarFill;
+
+#[derive(Component)]
+struct BossNameText;
+
+impl Plugin for BossHealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_boss_health_bar)
+            .add_systems(Update, update_boss_health_bar);
+    }
+}
+
+fn setup_boss_health_bar(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
+) {
+    let font_handle = required.font(&asset_server, "fonts/NicoPaint-Monospaced.ttf");
+
+    // Container at the bottom of the screen, hidden until a boss shows up
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(30.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            BossHealthBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Orc Warlord"),
+                TextFont {
+                    font: font_handle,
+                    font_size: 32.0,
+                    ..default()
+                },
+                BossNameText,
+            ));
+
+            // Bar background
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(BOSS_BAR_WIDTH),
+                        height: Val::Px(BOSS_BAR_HEIGHT),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                ))
+                .with_children(|parent| {
+                    // Bar fill
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.7, 0.05, 0.05)),
+                        BossHealthBarFill,
+                    ));
+                });
+        });
+}
+
+fn update_boss_health_bar(
+    boss_query: Query<(&Health, &Boss, &OrcEnemy)>,
+    mut bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut fill_query: Query<&mut Node, With<BossHealthBarFill>>,
+    mut text_query: Query<&mut Text, With<BossNameText>>,
+) {
+    let Ok(mut bar_visibility) = bar_query.get_single_mut() else {
+        return;
+    };
+
+    // Hide the bar when there is no living boss
+    let (health, boss) = match boss_query.get_single() {
+        Ok((health, boss, orc)) if orc.state != OrcState::Dying => (health, boss),
+        _ => {
+            *bar_visibility = Visibility::Hidden;
+            return;
+        }
+    };
+    *bar_visibility = Visibility::Visible;
+
+    if let Ok(mut style) = fill_query.get_single_mut() {
+        let health_percent = (health.health / boss.max_health * 100.0).clamp(0.0, 100.0);
+        style.width = Val::Percent(health_percent);
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        let title = match boss.phase {
+            BossPhase::First => "Orc Warlord",
+            BossPhase::Second => "Orc Warlord - Enraged",
+            BossPhase::Third => "Orc Warlord - Berserk",
+        };
+        if **text != title {
+            **text = title.to_string();
+        }
+    }
+}
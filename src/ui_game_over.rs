@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use crate::leaderboard::{record_run, Leaderboard, RunStats};
+use crate::loading::{AppState, RequiredAssets};
+use crate::ui_orc_counter::OrcDeathCounter;
+
+// Leaderboard rows listed under the run that just ended
+const GAME_OVER_ROWS: usize = 10;
+
+pub struct GameOverPlugin;
+
+#[derive(Resource)]
+struct GameOverFont(Handle<Font>);
+
+#[derive(Component)]
+struct GameOverScreen;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_game_over_font)
+            // After record_run, so the leaderboard already holds this run
+            .add_systems(OnEnter(AppState::GameOver), spawn_game_over_screen.after(record_run))
+            .add_systems(OnExit(AppState::GameOver), despawn_game_over_screen)
+            .add_systems(Update, back_to_main_menu.run_if(in_state(AppState::GameOver)));
+    }
+}
+
+fn load_game_over_font(mut commands: Commands, asset_server: Res<AssetServer>, mut required: ResMut<RequiredAssets>) {
+    let font = required.font(&asset_server, "fonts/NicoPaint-Monospaced.ttf");
+    commands.insert_resource(GameOverFont(font));
+}
+
+fn spawn_game_over_screen(
+    mut commands: Commands,
+    font: Res<GameOverFont>,
+    stats: Res<RunStats>,
+    kill_count: Res<OrcDeathCounter>,
+    leaderboard: Res<Leaderboard>,
+) {
+    let secs = stats.survival_secs as u32;
+    let summary = format!(
+        "Orcs killed: {}\nSurvived: {}:{:02}\nSpells cast: {} ({} misspelled)\nTyping accuracy: {:.0}%",
+        kill_count.count,
+        secs / 60,
+        secs % 60,
+        stats.spells_cast,
+        stats.misspells,
+        stats.accuracy() * 100.0,
+    );
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.15, 0.0, 0.0, 0.85)),
+            GlobalZIndex(50),
+            GameOverScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("You died"),
+                TextFont { font: font.0.clone(), font_size: 64.0, ..default() },
+                TextColor(Color::srgb(0.9, 0.1, 0.1)),
+            ));
+            parent.spawn((
+                Text::new(summary),
+                TextFont { font: font.0.clone(), font_size: 24.0, ..default() },
+                TextLayout::new_with_justify(JustifyText::Center),
+            ));
+
+            leaderboard.spawn_lines(parent, &font.0, GAME_OVER_ROWS);
+
+            parent.spawn((
+                Text::new("Press Enter to return to the main menu"),
+                TextFont { font: font.0.clone(), font_size: 18.0, ..default() },
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+}
+
+fn despawn_game_over_screen(mut commands: Commands, screen_query: Query<Entity, With<GameOverScreen>>) {
+    for entity in screen_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn back_to_main_menu(kbd: Res<ButtonInput<KeyCode>>, mut next_state: ResMut<NextState<AppState>>) {
+    if kbd.just_pressed(KeyCode::Enter) {
+        next_state.set(AppState::MainMenu);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::loading::RequiredAssets;
 use crate::player_code::Player;
 use crate::player_code::Health;
 use crate::player_code::Shield;
@@ -58,14 +59,15 @@ fn setup_max_values(
 fn setup_health_display(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
 ) {
     // Define health bar dimensions
     let health_bar_width = 349.0 * 1.5;
     let health_bar_height = 48.0 * 1.5;
 
-    let hearts_image = asset_server.load("UI/hp_containers.png");
-    let red = asset_server.load("UI/red.png");
-    let blue = asset_server.load("UI/blue.png");
+    let hearts_image = required.image(&asset_server, "UI/hp_containers.png", UVec2::new(349, 48));
+    let red = required.image(&asset_server, "UI/red.png", UVec2::new(258, 30));
+    let blue = required.image(&asset_server, "UI/blue.png", UVec2::new(258, 34));
 
     // Container node
     commands
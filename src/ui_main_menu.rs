@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+use crate::leaderboard::Leaderboard;
+use crate::loading::{AppState, RequiredAssets};
+use crate::save::{read_save, ActiveSlot, NewGameEvent, PendingLoad, SAVE_SLOTS};
+
+// Keys picking the save slots, in slot order
+const SLOT_KEYS: [KeyCode; SAVE_SLOTS] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+// Leaderboard rows listed under the save slots
+const MENU_LEADERBOARD_ROWS: usize = 5;
+
+pub struct MainMenuPlugin;
+
+#[derive(Resource)]
+struct MainMenuFont(Handle<Font>);
+
+// Root of the menu, despawned when the game starts
+#[derive(Component)]
+struct MainMenu;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_menu_font)
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnExit(AppState::MainMenu), despawn_main_menu)
+            .add_systems(Update, choose_slot.run_if(in_state(AppState::MainMenu)));
+    }
+}
+
+fn load_menu_font(mut commands: Commands, asset_server: Res<AssetServer>, mut required: ResMut<RequiredAssets>) {
+    let font = required.font(&asset_server, "fonts/NicoPaint-Monospaced.ttf");
+    commands.insert_resource(MainMenuFont(font));
+}
+
+fn spawn_main_menu(mut commands: Commands, font: Res<MainMenuFont>, leaderboard: Res<Leaderboard>) {
+    let text_font = |font_size: f32| TextFont {
+        font: font.0.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            GlobalZIndex(50),
+            MainMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn((Text::new("Misspelled"), text_font(64.0)));
+
+            for slot in 1..=SAVE_SLOTS {
+                let description = match read_save(slot) {
+                    Some(save) => save.summary(),
+                    None => "New game".to_string(),
+                };
+                parent.spawn((Text::new(format!("[{}] {}", slot, description)), text_font(28.0)));
+            }
+
+            parent.spawn((
+                Text::new("Press a slot number to play, cast \"save\" to save into it"),
+                text_font(18.0),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+
+            leaderboard.spawn_lines(parent, &font.0, MENU_LEADERBOARD_ROWS);
+        });
+}
+
+fn despawn_main_menu(mut commands: Commands, menu_query: Query<Entity, With<MainMenu>>) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Continue the run saved in the chosen slot, or start a fresh one there
+fn choose_slot(
+    mut commands: Commands,
+    kbd: Res<ButtonInput<KeyCode>>,
+    mut active_slot: ResMut<ActiveSlot>,
+    mut new_game_events: EventWriter<NewGameEvent>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let Some(index) = SLOT_KEYS.iter().position(|key| kbd.just_pressed(*key)) else {
+        return;
+    };
+    let slot = index + 1;
+    active_slot.0 = slot;
+
+    match read_save(slot) {
+        Some(save) => {
+            info!("Loading slot {}", slot);
+            commands.insert_resource(PendingLoad(save));
+        }
+        None => {
+            info!("Starting a new game in slot {}", slot);
+            new_game_events.send(NewGameEvent);
+        }
+    }
+    next_state.set(AppState::InGame);
+}
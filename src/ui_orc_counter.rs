@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::loading::RequiredAssets;
 
 // Resource to track the number of orcs killed
 #[derive(Resource)]
@@ -15,7 +16,10 @@ impl Default for OrcDeathCounter {
 
 // Event emitted when an orc dies
 #[derive(Event)]
-pub struct OrcDeathEvent;
+pub struct OrcDeathEvent {
+    pub entity: Entity,
+    pub position: Vec2,
+}
 
 // Component for the counter UI
 #[derive(Component)]
@@ -39,10 +43,11 @@ impl Plugin for OrcDeathCounterPlugin {
 fn setup_kill_counter_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut required: ResMut<RequiredAssets>,
     death_counter: Res<OrcDeathCounter>,
 ) {
-    // Try to load the font, if it fails, use the default font
-    let font_handle = asset_server.load("fonts/NicoPaint-Monospaced.ttf");
+    // Falls back to the default font if it fails to load
+    let font_handle = required.font(&asset_server, "fonts/NicoPaint-Monospaced.ttf");
 
     commands
         .spawn(Node {
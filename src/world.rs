@@ -1,61 +1,54 @@
 use bevy::prelude::Commands;
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
 use bevy_rapier2d::prelude::*;
 
-const WALL_WIDTH: f32 = 3880.0;
-const WALL_HEIGHT: f32 = 120.0;
+/// Marker for background images of the level
+#[derive(Component)]
+pub struct Background;
 
+/// Marker for static level geometry, used to bake the navigation grid
 #[derive(Component)]
-struct LibraryBackground;
-pub fn setup_world(
-    mut commands: Commands,
-    texture: Handle<Image>,
+pub struct Wall;
 
-) {
+/// Spawn a background image with its top-left corner at `top_left`
+pub fn spawn_background(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    top_left: Vec3,
+    scale: f32,
+) -> Entity {
     commands
         .spawn((
-        Sprite {
-            image: texture,
-            ..default()
-        },
-        Transform {
-            translation: Vec3::new(0.0, 32.0, -1.0), // Position at z=-1 so it's behind other entities
-            scale: Vec3::splat(2.0),
-            ..default()
-        },
-        LibraryBackground,
-    ));
+            Sprite {
+                image: texture,
+                anchor: Anchor::TopLeft,
+                ..default()
+            },
+            Transform {
+                translation: top_left, // Use a negative z so it's behind other entities
+                scale: Vec3::splat(scale),
+                ..default()
+            },
+            Background,
+        ))
+        .id()
+}
 
-    //Top Wall collider
-    commands.spawn((
-        Collider::cuboid(WALL_WIDTH, WALL_HEIGHT),
-        Transform {
-            translation: Vec3::new(0.0, WALL_HEIGHT+32.0, 0.0),
-            ..Default::default()
-        }
-    ));
-    // Bottom
-    commands.spawn((
-        Collider::cuboid(WALL_WIDTH, WALL_HEIGHT),
-        Transform {
-            translation: Vec3::new(0.0, -(2.*WALL_HEIGHT + 20.0), 0.0),
-            ..Default::default()
-        }
-    ));
-    //Left
-    commands.spawn((
-        Collider::cuboid(WALL_HEIGHT, WALL_HEIGHT),
-        Transform {
-            translation: Vec3::new(-WALL_WIDTH, -32.0, 0.0),
-            ..Default::default()
-        }
-    ));
-    //Right
-    commands.spawn((
-        Collider::cuboid(WALL_HEIGHT, WALL_HEIGHT),
-        Transform {
-            translation: Vec3::new(WALL_WIDTH, -32.0, 0.0),
-            ..Default::default()
-        }
-    ));
-}
\ No newline at end of file
+/// Spawn an invisible, static wall collider
+pub fn spawn_wall(
+    commands: &mut Commands,
+    center: Vec2,
+    half_extents: Vec2,
+) -> Entity {
+    commands
+        .spawn((
+            Collider::cuboid(half_extents.x, half_extents.y),
+            Wall,
+            Transform {
+                translation: center.extend(0.0),
+                ..Default::default()
+            },
+        ))
+        .id()
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_rapier2d::prelude::PhysicsSet;
+
+/// z of a y-sorted sprite standing on the world origin. Sits between the
+/// map layers (below 0) and foreground layers (100).
+pub const YSORT_Z: f32 = 50.0;
+/// z change per world unit of y, small enough to cover the biggest levels
+const YSORT_Z_PER_UNIT: f32 = 0.001;
+
+/// Derive the draw order from the world y of where the entity touches the
+/// ground, so whatever is lower on screen is drawn in front.
+/// Only meant for top-level entities, children keep their local z.
+#[derive(Component, Default)]
+pub struct YSort {
+    /// From the transform down to the feet, usually negative
+    pub offset: f32,
+}
+
+pub struct YSortPlugin;
+
+impl Plugin for YSortPlugin {
+    fn build(&self, app: &mut App) {
+        // After physics moved everything, before the z ends up in GlobalTransform
+        app.add_systems(
+            PostUpdate,
+            apply_y_sort
+                .after(PhysicsSet::Writeback)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+fn apply_y_sort(mut query: Query<(&YSort, &mut Transform)>) {
+    for (y_sort, mut transform) in query.iter_mut() {
+        let z = YSORT_Z - (transform.translation.y + y_sort.offset) * YSORT_Z_PER_UNIT;
+        // Avoid flagging every transform as changed when nothing moved
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use bevy::prelude::*;
+
+use misspelled::blink::{BlinkPhase, BlinkingEffect, BLINK_DISTANCE, BLINK_PHASE_DURATION};
+use misspelled::headless_app;
+use misspelled::HEADLESS_TIMESTEP;
+
+use common::{cast, player, position, step_until};
+
+fn phase(app: &App, player: Entity) -> Option<BlinkPhase> {
+    app.world().get::<BlinkingEffect>(player).map(|blink| blink.phase)
+}
+
+#[test]
+fn blink_goes_through_its_phases_and_lands_ahead() {
+    let mut app = headless_app();
+    app.update();
+    let player = player(&mut app);
+    let start = position(&app, player);
+
+    cast(&mut app, "blink");
+    assert_eq!(phase(&app, player), Some(BlinkPhase::Disappearing));
+
+    // Phase of every frame until the blink is over, and where the player was
+    let mut frames = Vec::new();
+    let done = step_until(&mut app, 120, |app| {
+        frames.push((phase(app, player), position(app, player)));
+        phase(app, player).is_none()
+    });
+    assert!(done.is_some(), "the blink should be over within two seconds");
+
+    let mut phases: Vec<Option<BlinkPhase>> = frames.iter().map(|(phase, _)| *phase).collect();
+    phases.dedup();
+    assert_eq!(
+        phases,
+        vec![
+            Some(BlinkPhase::Disappearing),
+            Some(BlinkPhase::Moving),
+            Some(BlinkPhase::Reappearing),
+            Some(BlinkPhase::Complete),
+            None,
+        ]
+    );
+
+    // The player stays put until the Moving phase, then is at the target right away
+    let target = start + Vec2::new(BLINK_DISTANCE, 0.0);
+    for (phase, at) in frames.iter() {
+        let expected = match phase {
+            Some(BlinkPhase::Disappearing) | Some(BlinkPhase::Moving) => start,
+            _ => target,
+        };
+        assert!(at.distance(expected) < 1.0, "{:?} at {:?}, expected {:?}", phase, at, expected);
+    }
+
+    // Only the Moving phase is instant, the others last their full duration
+    let phase_frames = (BLINK_PHASE_DURATION / HEADLESS_TIMESTEP).round() as usize;
+    let count = |wanted| frames.iter().filter(|(phase, _)| *phase == Some(wanted)).count();
+    assert_eq!(count(BlinkPhase::Moving), 1);
+    assert_eq!(count(BlinkPhase::Complete), 1);
+    assert!(count(BlinkPhase::Disappearing).abs_diff(phase_frames) <= 2);
+    assert!(count(BlinkPhase::Reappearing).abs_diff(phase_frames) <= 1);
+
+    // The player's own sprite is back
+    let sprite = app.world().get::<Sprite>(player).unwrap();
+    assert_eq!(sprite.custom_size, None);
+}
@@ -0,0 +1,114 @@
+//! Helpers shared by the scenario tests, driving a `misspelled::headless_app`
+//! through its keyboard like a player would.
+
+#![allow(dead_code)]
+
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKeyCode};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+
+use misspelled::headless_app;
+use misspelled::level::OrcSpawnPoint;
+use misspelled::orc::OrcEnemy;
+use misspelled::player_code::Player;
+use misspelled::spell::{SpellCastEvent, SpellType};
+
+/// Run `frames` updates of the app
+pub fn step(app: &mut App, frames: u32) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Run updates until `done` holds, at most `max_frames` of them.
+/// Returns how many updates it took, `None` if `done` never held.
+pub fn step_until(app: &mut App, max_frames: u32, mut done: impl FnMut(&mut App) -> bool) -> Option<u32> {
+    for frame in 0..=max_frames {
+        if done(app) {
+            return Some(frame);
+        }
+        if frame < max_frames {
+            app.update();
+        }
+    }
+    None
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, logical_key: Key, state: ButtonState) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key,
+        state,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+/// Press and release `key_code` over two frames
+pub fn tap(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    send_key(app, key_code, logical_key.clone(), ButtonState::Pressed);
+    app.update();
+    send_key(app, key_code, logical_key, ButtonState::Released);
+    app.update();
+}
+
+/// Type `text` into the spell stack, one character per tap, without casting it
+pub fn type_text(app: &mut App, text: &str) {
+    // The letters don't need a physical key, an unidentified one keeps them
+    // from also reading as arrow keys or shortcuts
+    let key_code = KeyCode::Unidentified(NativeKeyCode::Unidentified);
+    for c in text.chars() {
+        tap(app, key_code, Key::Character(c.to_string().into()));
+    }
+}
+
+/// Open the spell stack, type `spell` and cast it with Enter
+pub fn cast(app: &mut App, spell: &str) {
+    tap(app, KeyCode::Enter, Key::Enter);
+    type_text(app, spell);
+    tap(app, KeyCode::Enter, Key::Enter);
+}
+
+/// Every spell cast so far, filled once `log_casts` was called on the app
+#[derive(Resource, Default)]
+pub struct CastLog(pub Vec<(SpellType, String)>);
+
+pub fn log_casts(app: &mut App) {
+    app.init_resource::<CastLog>().add_systems(Update, record_casts);
+}
+
+fn record_casts(mut spell_events: EventReader<SpellCastEvent>, mut log: ResMut<CastLog>) {
+    for event in spell_events.read() {
+        log.0.push((event.spell_type, event.spell_name.clone()));
+    }
+}
+
+pub fn player(app: &mut App) -> Entity {
+    let mut query = app.world_mut().query_filtered::<Entity, With<Player>>();
+    query.single(app.world())
+}
+
+pub fn orcs(app: &mut App) -> Vec<Entity> {
+    let mut query = app.world_mut().query_filtered::<Entity, With<OrcEnemy>>();
+    query.iter(app.world()).collect()
+}
+
+pub fn position(app: &App, entity: Entity) -> Vec2 {
+    app.world().get::<Transform>(entity).unwrap().translation.truncate()
+}
+
+/// Headless app with the player at the origin, facing right, and one orc `offset` away from them
+pub fn arena_with_orc(offset: Vec2) -> (App, Entity) {
+    let mut app = headless_app();
+    app.update();
+
+    let player = player(&mut app);
+    let orc_position = position(&app, player) + offset;
+    app.world_mut()
+        .spawn((OrcSpawnPoint, Transform::from_translation(orc_position.extend(0.0))));
+    step(&mut app, 2);
+
+    let orcs = orcs(&mut app);
+    assert_eq!(orcs.len(), 1, "the spawn point should have spawned one orc");
+    (app, orcs[0])
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bevy::prelude::*;
+
+use misspelled::fireball::{Fireball, FIREBALL_DAMAGE};
+use misspelled::orc::collision::{AttackHitbox, HurtHitbox};
+use misspelled::orc::{OrcEnemy, OrcState};
+use misspelled::player_code::Health;
+use misspelled::ui_orc_counter::OrcDeathCounter;
+
+use common::{arena_with_orc, cast, step, step_until};
+
+fn orc_health(app: &App, orc: Entity) -> f32 {
+    app.world().get::<Health>(orc).unwrap().health
+}
+
+fn orc_state(app: &App, orc: Entity) -> OrcState {
+    app.world().get::<OrcEnemy>(orc).unwrap().state
+}
+
+fn hitboxes(app: &mut App, orc: Entity) -> usize {
+    let mut hurt = app.world_mut().query::<&HurtHitbox>();
+    let hurt = hurt.iter(app.world()).filter(|hitbox| hitbox.owner == orc).count();
+    let mut attack = app.world_mut().query::<&AttackHitbox>();
+    let attack = attack.iter(app.world()).filter(|hitbox| hitbox.owner == orc).count();
+    hurt + attack
+}
+
+fn fireballs(app: &mut App) -> usize {
+    let mut query = app.world_mut().query_filtered::<Entity, With<Fireball>>();
+    query.iter(app.world()).count()
+}
+
+#[test]
+fn fireball_hurts_an_orc_it_does_not_kill() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(200.0, 0.0));
+    app.world_mut().get_mut::<Health>(orc).unwrap().health = FIREBALL_DAMAGE * 2.0;
+
+    cast(&mut app, "fireball");
+    assert_eq!(fireballs(&mut app), 1);
+    let hit = step_until(&mut app, 120, |app| orc_health(app, orc) < FIREBALL_DAMAGE * 2.0);
+    assert!(hit.is_some(), "the fireball should reach the orc within two seconds");
+
+    assert_eq!(orc_health(&app, orc), FIREBALL_DAMAGE);
+    assert_ne!(orc_state(&app, orc), OrcState::Dying);
+
+    // The fireball is gone after the hit instead of going on to a second one
+    step(&mut app, 2);
+    assert_eq!(fireballs(&mut app), 0);
+    assert_eq!(orc_health(&app, orc), FIREBALL_DAMAGE);
+    assert_eq!(app.world().resource::<OrcDeathCounter>().count, 0);
+}
+
+#[test]
+fn second_fireball_kills_the_orc() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(200.0, 0.0));
+    app.world_mut().get_mut::<Health>(orc).unwrap().health = FIREBALL_DAMAGE * 2.0;
+
+    cast(&mut app, "fireball");
+    let hit = step_until(&mut app, 120, |app| orc_health(app, orc) < FIREBALL_DAMAGE * 2.0);
+    assert!(hit.is_some(), "the first fireball should reach the orc");
+
+    cast(&mut app, "fireball");
+    // Checked every frame, a dead orc fades out and is despawned after a while
+    let killed = step_until(&mut app, 120, |app| orc_state(app, orc) == OrcState::Dying);
+    assert!(killed.is_some(), "the second fireball should kill the orc");
+    assert!(orc_health(&app, orc) <= 0.0);
+
+    step(&mut app, 1);
+    assert_eq!(app.world().resource::<OrcDeathCounter>().count, 1);
+}
+
+#[test]
+fn dying_orc_loses_its_hitboxes() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(150.0, 0.0));
+    assert_eq!(hitboxes(&mut app, orc), 2, "a living orc can hurt and be hurt");
+
+    cast(&mut app, "fireball");
+    let killed = step_until(&mut app, 120, |app| orc_state(app, orc) == OrcState::Dying);
+    assert!(killed.is_some(), "the fireball should kill the orc");
+    step(&mut app, 1);
+
+    // Still there for its death animation, but nothing left to hit or be hit by
+    assert!(app.world().get::<OrcEnemy>(orc).is_some());
+    assert_eq!(hitboxes(&mut app, orc), 0);
+
+    // Another fireball flies through the body
+    cast(&mut app, "fireball");
+    step(&mut app, 60);
+    assert_eq!(app.world().resource::<OrcDeathCounter>().count, 1);
+}
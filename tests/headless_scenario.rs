@@ -0,0 +1,39 @@
+mod common;
+
+use bevy::prelude::*;
+
+use misspelled::orc::{OrcEnemy, OrcState};
+use misspelled::player_code::Health;
+use misspelled::ui_orc_counter::OrcDeathCounter;
+
+use common::{arena_with_orc, cast, step};
+
+#[test]
+fn fireball_kills_the_orc_in_front() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(120.0, 0.0));
+
+    cast(&mut app, "fireball");
+    // Check every frame, a dead orc fades out and is despawned after a while
+    let mut frames = 0;
+    while app.world().get::<OrcEnemy>(orc).unwrap().state != OrcState::Dying {
+        assert!(frames < 120, "the fireball should reach the orc within two seconds");
+        step(&mut app, 1);
+        frames += 1;
+    }
+
+    assert!(app.world().get::<Health>(orc).unwrap().health <= 0.0);
+    // The counter reads the death event on the next frame at the latest
+    step(&mut app, 1);
+    assert_eq!(app.world().resource::<OrcDeathCounter>().count, 1);
+}
+
+#[test]
+fn fireball_misses_the_orc_behind() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(-120.0, 0.0));
+
+    cast(&mut app, "fireball");
+    step(&mut app, 30);
+
+    assert_ne!(app.world().get::<OrcEnemy>(orc).unwrap().state, OrcState::Dying);
+    assert_eq!(app.world().resource::<OrcDeathCounter>().count, 0);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use bevy::prelude::*;
+
+use misspelled::orc::behaviour::{ATTACK_ANIM_DURATION, ATTACK_COOLDOWN, ATTACK_RANGE};
+use misspelled::orc::{OrcEnemy, OrcState};
+use misspelled::HEADLESS_TIMESTEP;
+
+use common::{arena_with_orc, step};
+
+fn secs_to_frames(secs: f32) -> u32 {
+    (secs / HEADLESS_TIMESTEP).round() as u32
+}
+
+// State of the orc on each of the next `frames` frames
+fn record_states(app: &mut App, orc: Entity, frames: u32) -> Vec<OrcState> {
+    (0..frames)
+        .map(|_| {
+            step(app, 1);
+            app.world().get::<OrcEnemy>(orc).unwrap().state
+        })
+        .collect()
+}
+
+// Frames on which an attack starts
+fn attack_starts(states: &[OrcState]) -> Vec<u32> {
+    (1..states.len())
+        .filter(|&i| states[i] == OrcState::Attacking && states[i - 1] != OrcState::Attacking)
+        .map(|i| i as u32)
+        .collect()
+}
+
+#[test]
+fn orc_in_range_swings_then_rests() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(ATTACK_RANGE * 0.75, 0.0));
+
+    let states = record_states(&mut app, orc, secs_to_frames(4.0));
+
+    // No walking over, the player is already in reach
+    let first_attack = states.iter().position(|state| *state == OrcState::Attacking);
+    assert!(first_attack.is_some_and(|frame| frame <= 2), "the orc should attack right away");
+    assert!(!states.contains(&OrcState::Walking));
+
+    // Each swing plays the attack animation, then the orc stands idle through its cooldown.
+    // Measured on the second swing, the first one may have started before recording.
+    let second_attack = attack_starts(&states)[0] as usize;
+    let swing = states[second_attack..]
+        .iter()
+        .take_while(|state| **state == OrcState::Attacking)
+        .count() as u32;
+    assert!(swing.abs_diff(secs_to_frames(ATTACK_ANIM_DURATION)) <= 1, "swing lasted {} frames", swing);
+    let rest = states[second_attack + swing as usize..]
+        .iter()
+        .take_while(|state| **state == OrcState::Idle)
+        .count() as u32;
+    assert!(rest.abs_diff(secs_to_frames(ATTACK_COOLDOWN)) <= 1, "rest lasted {} frames", rest);
+}
+
+#[test]
+fn orc_attacks_at_a_steady_cadence() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(ATTACK_RANGE * 0.75, 0.0));
+
+    let states = record_states(&mut app, orc, secs_to_frames(5.0));
+    let starts = attack_starts(&states);
+    assert!(starts.len() >= 3, "expected at least 3 attacks in 5 seconds, got {:?}", starts);
+
+    let period = secs_to_frames(ATTACK_ANIM_DURATION + ATTACK_COOLDOWN);
+    for pair in starts.windows(2) {
+        let gap = pair[1] - pair[0];
+        assert!(gap.abs_diff(period) <= 1, "attacks {:?} are {} frames apart, expected {}", pair, gap, period);
+    }
+}
+
+#[test]
+fn orc_out_of_range_chases_instead_of_attacking() {
+    let (mut app, orc) = arena_with_orc(Vec2::new(ATTACK_RANGE * 3.0, 0.0));
+
+    let states = record_states(&mut app, orc, 10);
+
+    assert!(states.iter().all(|state| *state == OrcState::Walking), "got {:?}", states);
+}
@@ -0,0 +1,111 @@
+mod common;
+
+use bevy::prelude::*;
+
+use misspelled::headless_app;
+use misspelled::loading::AppState;
+use misspelled::player_code::{
+    Health, Invulnerable, PlayerDamageEvent, Shield, INVULNERABILITY_DURATION, PLAYER_MAX_HEALTH,
+};
+use misspelled::HEADLESS_TIMESTEP;
+
+use common::{player, step, step_until};
+
+fn player_with_shield(shield: f32) -> (App, Entity) {
+    let mut app = headless_app();
+    app.update();
+    let player = player(&mut app);
+    *app.world_mut().get_mut::<Shield>(player).unwrap() = Shield::new(shield);
+    (app, player)
+}
+
+fn hurt(app: &mut App, damage: f32) {
+    app.world_mut().send_event(PlayerDamageEvent { damage });
+    app.update();
+}
+
+fn health(app: &App, player: Entity) -> f32 {
+    app.world().get::<Health>(player).unwrap().health
+}
+
+fn shield(app: &App, player: Entity) -> f32 {
+    app.world().get::<Shield>(player).unwrap().shield
+}
+
+fn is_invulnerable(app: &App, player: Entity) -> bool {
+    app.world().get::<Invulnerable>(player).is_some()
+}
+
+#[test]
+fn damage_without_a_shield_goes_to_health() {
+    let (mut app, player) = player_with_shield(0.0);
+
+    hurt(&mut app, 3.0);
+
+    assert_eq!(health(&app, player), PLAYER_MAX_HEALTH - 3.0);
+    assert_eq!(shield(&app, player), 0.0);
+}
+
+#[test]
+fn shield_absorbs_damage_before_health() {
+    let (mut app, player) = player_with_shield(5.0);
+
+    hurt(&mut app, 3.0);
+
+    assert_eq!(shield(&app, player), 2.0);
+    assert_eq!(health(&app, player), PLAYER_MAX_HEALTH);
+}
+
+#[test]
+fn damage_past_the_shield_goes_to_health() {
+    let (mut app, player) = player_with_shield(2.0);
+
+    hurt(&mut app, 5.0);
+
+    assert_eq!(shield(&app, player), 0.0);
+    assert_eq!(health(&app, player), PLAYER_MAX_HEALTH - 3.0);
+}
+
+#[test]
+fn a_hit_makes_the_player_invulnerable() {
+    let (mut app, player) = player_with_shield(0.0);
+
+    hurt(&mut app, 1.0);
+    assert!(is_invulnerable(&app, player));
+
+    hurt(&mut app, 1.0);
+    assert_eq!(health(&app, player), PLAYER_MAX_HEALTH - 1.0);
+}
+
+#[test]
+fn invulnerability_wears_off_after_its_duration() {
+    let (mut app, player) = player_with_shield(0.0);
+    let expected_frames = (INVULNERABILITY_DURATION / HEADLESS_TIMESTEP).round() as u32;
+
+    hurt(&mut app, 1.0);
+    let frames = step_until(&mut app, expected_frames * 2, |app| !is_invulnerable(app, player))
+        .expect("invulnerability should end");
+
+    // A frame either way for the order the damage and timer systems run in
+    assert!(
+        frames.abs_diff(expected_frames) <= 1,
+        "invulnerable for {} frames, expected {}",
+        frames,
+        expected_frames
+    );
+    let sprite = app.world().get::<Sprite>(player).unwrap();
+    assert_eq!(sprite.color.alpha(), 1.0);
+
+    hurt(&mut app, 1.0);
+    assert_eq!(health(&app, player), PLAYER_MAX_HEALTH - 2.0);
+}
+
+#[test]
+fn losing_all_health_ends_the_game() {
+    let (mut app, _) = player_with_shield(0.0);
+
+    hurt(&mut app, PLAYER_MAX_HEALTH);
+    step(&mut app, 1);
+
+    assert_eq!(*app.world().resource::<State<AppState>>().get(), AppState::GameOver);
+}
@@ -0,0 +1,148 @@
+mod common;
+
+use bevy::input::keyboard::Key;
+use bevy::prelude::*;
+
+use misspelled::headless_app;
+use misspelled::spell::{identify_spell, SpellStack, SpellType, UnlockedSpells};
+
+use common::{cast, log_casts, tap, type_text, CastLog};
+
+#[test]
+fn identify_spell_knows_every_spell() {
+    assert_eq!(identify_spell("fireball"), SpellType::Fireball);
+    assert_eq!(identify_spell("blink"), SpellType::Blink);
+    assert_eq!(identify_spell("shield"), SpellType::Shield);
+    assert_eq!(identify_spell("exit"), SpellType::Exit);
+    assert_eq!(identify_spell("spellbook"), SpellType::Spellbook);
+    assert_eq!(identify_spell("save"), SpellType::Save);
+}
+
+#[test]
+fn identify_spell_ignores_case() {
+    assert_eq!(identify_spell("FireBall"), SpellType::Fireball);
+    assert_eq!(identify_spell("BLINK"), SpellType::Blink);
+}
+
+#[test]
+fn identify_spell_rejects_misspellings() {
+    assert_eq!(identify_spell("firebal"), SpellType::Unknown);
+    assert_eq!(identify_spell("fire ball"), SpellType::Unknown);
+    assert_eq!(identify_spell(" shield"), SpellType::Unknown);
+    assert_eq!(identify_spell(""), SpellType::Unknown);
+}
+
+#[test]
+fn spell_stack_push_pop_and_toggle() {
+    let mut stack = SpellStack::default();
+    assert!(!stack.is_active());
+
+    stack.toggle();
+    assert!(stack.is_active());
+    for c in "blinks".chars() {
+        stack.push(c);
+    }
+    stack.pop();
+    assert_eq!(stack.as_string(), "blink");
+
+    // Closing keeps what was typed, opening again starts from scratch
+    stack.toggle();
+    assert_eq!(stack.as_string(), "blink");
+    stack.toggle();
+    assert_eq!(stack.as_string(), "");
+
+    // Nothing to remove is fine
+    stack.pop();
+    assert_eq!(stack.as_string(), "");
+}
+
+fn spell_app() -> App {
+    let mut app = headless_app();
+    log_casts(&mut app);
+    app.update();
+    app
+}
+
+fn typed(app: &App) -> String {
+    app.world().resource::<SpellStack>().as_string()
+}
+
+#[test]
+fn letters_are_ignored_until_the_stack_is_open() {
+    let mut app = spell_app();
+
+    type_text(&mut app, "blink");
+    assert!(!app.world().resource::<SpellStack>().is_active());
+    assert_eq!(typed(&app), "");
+
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+    assert!(app.world().resource::<SpellStack>().is_active());
+    type_text(&mut app, "blink");
+    assert_eq!(typed(&app), "blink");
+}
+
+#[test]
+fn backspace_removes_the_last_letter() {
+    let mut app = spell_app();
+
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+    type_text(&mut app, "shielf");
+    tap(&mut app, KeyCode::Backspace, Key::Backspace);
+    type_text(&mut app, "d");
+    assert_eq!(typed(&app), "shield");
+
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+    assert_eq!(app.world().resource::<CastLog>().0, vec![(SpellType::Shield, "shield".to_string())]);
+}
+
+#[test]
+fn escape_closes_the_stack_without_casting() {
+    let mut app = spell_app();
+
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+    type_text(&mut app, "fireball");
+    tap(&mut app, KeyCode::Escape, Key::Escape);
+
+    assert!(!app.world().resource::<SpellStack>().is_active());
+    assert!(app.world().resource::<CastLog>().0.is_empty());
+}
+
+#[test]
+fn enter_on_an_empty_stack_casts_nothing() {
+    let mut app = spell_app();
+
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+    tap(&mut app, KeyCode::Enter, Key::Enter);
+
+    assert!(app.world().resource::<SpellStack>().is_active());
+    assert!(app.world().resource::<CastLog>().0.is_empty());
+}
+
+#[test]
+fn casting_closes_the_stack() {
+    let mut app = spell_app();
+
+    cast(&mut app, "blink");
+
+    assert!(!app.world().resource::<SpellStack>().is_active());
+    assert_eq!(app.world().resource::<CastLog>().0, vec![(SpellType::Blink, "blink".to_string())]);
+}
+
+#[test]
+fn misspelled_and_locked_spells_fizzle() {
+    let mut app = spell_app();
+    app.world_mut().resource_mut::<UnlockedSpells>().spells = vec![SpellType::Fireball];
+
+    cast(&mut app, "fierball");
+    cast(&mut app, "blink");
+    cast(&mut app, "fireball");
+
+    assert_eq!(
+        app.world().resource::<CastLog>().0,
+        vec![
+            (SpellType::Unknown, "fierball".to_string()),
+            (SpellType::Unknown, "blink".to_string()),
+            (SpellType::Fireball, "fireball".to_string()),
+        ]
+    );
+}